@@ -1,6 +1,32 @@
 #[cfg(feature = "alloc")]
-use alloc::borrow::{Cow, ToOwned};
-use alloc::vec::Vec;
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+use core::{
+    cell::{Cell, RefCell},
+    cmp::{Ordering, Reverse},
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    },
+    ops::{Bound, Range, RangeFrom, RangeInclusive, RangeTo},
+    sync::atomic,
+    time::Duration,
+};
+#[cfg(feature = "std")]
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Mutex, PoisonError, RwLock},
+};
 
 /// A trait for upgrading the lifetime of a type.
 ///
@@ -56,23 +82,206 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<T> IntoStatic for Box<T>
+where
+    T: IntoStatic,
+{
+    type Static = Box<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> Box<T::Static> {
+        Box::new((*self).into_static())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> IntoStatic for Box<[T]>
+where
+    T: IntoStatic,
+{
+    type Static = Box<[T::Static]>;
+
+    #[inline]
+    fn into_static(self) -> Box<[T::Static]> {
+        Vec::from(self).into_static().into_boxed_slice()
+    }
+}
+
+/// Unwraps the inner value if this is the only `Rc` pointing to it,
+/// otherwise the inner value is cloned.
+#[cfg(feature = "alloc")]
+impl<T> IntoStatic for Rc<T>
+where
+    T: IntoStatic + Clone,
+{
+    type Static = Rc<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> Rc<T::Static> {
+        let inner = Rc::try_unwrap(self).unwrap_or_else(|rc| T::clone(&rc));
+        Rc::new(inner.into_static())
+    }
+}
+
+/// Unwraps the inner value if this is the only `Arc` pointing to it,
+/// otherwise the inner value is cloned.
+#[cfg(feature = "alloc")]
+impl<T> IntoStatic for Arc<T>
+where
+    T: IntoStatic + Clone,
+{
+    type Static = Arc<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> Arc<T::Static> {
+        let inner = Arc::try_unwrap(self).unwrap_or_else(|arc| T::clone(&arc));
+        Arc::new(inner.into_static())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> IntoStatic for VecDeque<T>
+where
+    T: IntoStatic,
+{
+    type Static = VecDeque<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> VecDeque<T::Static> {
+        self.into_iter().map(IntoStatic::into_static).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> IntoStatic for LinkedList<T>
+where
+    T: IntoStatic,
+{
+    type Static = LinkedList<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> LinkedList<T::Static> {
+        self.into_iter().map(IntoStatic::into_static).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> IntoStatic for BinaryHeap<T>
+where
+    T: IntoStatic,
+    T::Static: Ord,
+{
+    type Static = BinaryHeap<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> BinaryHeap<T::Static> {
+        self.into_iter().map(IntoStatic::into_static).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> IntoStatic for BTreeMap<K, V>
+where
+    K: IntoStatic,
+    K::Static: Ord,
+    V: IntoStatic,
+{
+    type Static = BTreeMap<K::Static, V::Static>;
+
+    #[inline]
+    fn into_static(self) -> BTreeMap<K::Static, V::Static> {
+        self.into_iter()
+            .map(|(key, value)| (key.into_static(), value.into_static()))
+            .collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> IntoStatic for BTreeSet<T>
+where
+    T: IntoStatic,
+    T::Static: Ord,
+{
+    type Static = BTreeSet<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> BTreeSet<T::Static> {
+        self.into_iter().map(IntoStatic::into_static).collect()
+    }
+}
+
+/// The hasher of the map is cloned into the new map.
+#[cfg(feature = "std")]
+impl<K, V, S> IntoStatic for HashMap<K, V, S>
+where
+    K: IntoStatic,
+    K::Static: Eq + Hash,
+    V: IntoStatic,
+    S: BuildHasher + Clone + 'static,
+{
+    type Static = HashMap<K::Static, V::Static, S>;
+
+    #[inline]
+    fn into_static(self) -> HashMap<K::Static, V::Static, S> {
+        let mut map = HashMap::with_capacity_and_hasher(self.len(), self.hasher().clone());
+        map.extend(
+            self.into_iter()
+                .map(|(key, value)| (key.into_static(), value.into_static())),
+        );
+        map
+    }
+}
+
+/// The hasher of the set is cloned into the new set.
+#[cfg(feature = "std")]
+impl<T, S> IntoStatic for HashSet<T, S>
+where
+    T: IntoStatic,
+    T::Static: Eq + Hash,
+    S: BuildHasher + Clone + 'static,
+{
+    type Static = HashSet<T::Static, S>;
+
+    #[inline]
+    fn into_static(self) -> HashSet<T::Static, S> {
+        let mut set = HashSet::with_capacity_and_hasher(self.len(), self.hasher().clone());
+        set.extend(self.into_iter().map(IntoStatic::into_static));
+        set
+    }
+}
+
+impl<T, const N: usize> IntoStatic for [T; N]
+where
+    T: IntoStatic,
+{
+    type Static = [T::Static; N];
+
+    #[inline]
+    fn into_static(self) -> [T::Static; N] {
+        self.map(IntoStatic::into_static)
+    }
+}
+
 macro_rules! impl_tuple {
-    ($($T:ident),+) => {
-        impl<$($T),+> IntoStatic for ($($T),+)
+    ($($T:ident),*) => {
+        impl<$($T),*> IntoStatic for ($($T,)*)
         where
-            $($T: IntoStatic),+
+            $($T: IntoStatic),*
         {
-            type Static = ($($T::Static),+);
+            type Static = ($($T::Static,)*);
 
-            #[allow(non_snake_case)]
+            #[allow(non_snake_case, clippy::unused_unit)]
             fn into_static(self) -> Self::Static {
-                let ($($T,)+) = self;
-                ($($T.into_static()),+)
+                let ($($T,)*) = self;
+                ($($T.into_static(),)*)
             }
         }
     };
 }
 
+impl_tuple!();
+impl_tuple!(A);
 impl_tuple!(A, B);
 impl_tuple!(A, B, C);
 impl_tuple!(A, B, C, D);
@@ -81,6 +290,9 @@ impl_tuple!(A, B, C, D, E, F);
 impl_tuple!(A, B, C, D, E, F, G);
 impl_tuple!(A, B, C, D, E, F, G, H);
 impl_tuple!(A, B, C, D, E, F, G, H, I);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
 
 impl<T> IntoStatic for Option<T>
 where
@@ -94,7 +306,6 @@ where
     }
 }
 
-#[cfg(feature = "unstable")]
 impl<T, E> IntoStatic for Result<T, E>
 where
     T: IntoStatic,
@@ -109,6 +320,249 @@ where
     }
 }
 
+impl<T> IntoStatic for Range<T>
+where
+    T: IntoStatic,
+{
+    type Static = Range<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> Range<T::Static> {
+        self.start.into_static()..self.end.into_static()
+    }
+}
+
+impl<T> IntoStatic for RangeInclusive<T>
+where
+    T: IntoStatic,
+{
+    type Static = RangeInclusive<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> RangeInclusive<T::Static> {
+        let (start, end) = self.into_inner();
+        start.into_static()..=end.into_static()
+    }
+}
+
+impl<T> IntoStatic for RangeFrom<T>
+where
+    T: IntoStatic,
+{
+    type Static = RangeFrom<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> RangeFrom<T::Static> {
+        self.start.into_static()..
+    }
+}
+
+impl<T> IntoStatic for RangeTo<T>
+where
+    T: IntoStatic,
+{
+    type Static = RangeTo<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> RangeTo<T::Static> {
+        ..self.end.into_static()
+    }
+}
+
+impl<T> IntoStatic for Bound<T>
+where
+    T: IntoStatic,
+{
+    type Static = Bound<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> Bound<T::Static> {
+        match self {
+            Bound::Included(value) => Bound::Included(value.into_static()),
+            Bound::Excluded(value) => Bound::Excluded(value.into_static()),
+            Bound::Unbounded => Bound::Unbounded,
+        }
+    }
+}
+
+impl<T> IntoStatic for Reverse<T>
+where
+    T: IntoStatic,
+{
+    type Static = Reverse<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> Reverse<T::Static> {
+        Reverse(self.0.into_static())
+    }
+}
+
+impl<T> IntoStatic for Wrapping<T>
+where
+    T: IntoStatic,
+{
+    type Static = Wrapping<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> Wrapping<T::Static> {
+        Wrapping(self.0.into_static())
+    }
+}
+
+impl<T> IntoStatic for PhantomData<T>
+where
+    T: IntoStatic + ?Sized,
+{
+    type Static = PhantomData<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> PhantomData<T::Static> {
+        PhantomData
+    }
+}
+
+impl<T> IntoStatic for Cell<T>
+where
+    T: IntoStatic,
+{
+    type Static = Cell<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> Cell<T::Static> {
+        Cell::new(self.into_inner().into_static())
+    }
+}
+
+impl<T> IntoStatic for RefCell<T>
+where
+    T: IntoStatic,
+{
+    type Static = RefCell<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> RefCell<T::Static> {
+        RefCell::new(self.into_inner().into_static())
+    }
+}
+
+/// If the mutex is poisoned, the value is converted anyway.
+/// The returned mutex is not poisoned.
+#[cfg(feature = "std")]
+impl<T> IntoStatic for Mutex<T>
+where
+    T: IntoStatic,
+{
+    type Static = Mutex<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> Mutex<T::Static> {
+        let inner = self.into_inner().unwrap_or_else(PoisonError::into_inner);
+        Mutex::new(inner.into_static())
+    }
+}
+
+/// If the lock is poisoned, the value is converted anyway.
+/// The returned lock is not poisoned.
+#[cfg(feature = "std")]
+impl<T> IntoStatic for RwLock<T>
+where
+    T: IntoStatic,
+{
+    type Static = RwLock<T::Static>;
+
+    #[inline]
+    fn into_static(self) -> RwLock<T::Static> {
+        let inner = self.into_inner().unwrap_or_else(PoisonError::into_inner);
+        RwLock::new(inner.into_static())
+    }
+}
+
+/// A reference to the zero-sized `()` can always be replaced by a `'static` one.
+/// This allows `PhantomData<&'a ()>` to be converted.
+impl IntoStatic for &() {
+    type Static = &'static ();
+
+    #[inline]
+    fn into_static(self) -> &'static () {
+        &()
+    }
+}
+
+/// Implements `IntoStatic` for types without lifetimes by returning the value as is.
+macro_rules! impl_identity {
+    ($($(#[$attr:meta])* $T:ty),+ $(,)?) => {
+        $(
+            $(#[$attr])*
+            impl IntoStatic for $T {
+                type Static = $T;
+
+                #[inline]
+                fn into_static(self) -> $T {
+                    self
+                }
+            }
+        )+
+    };
+}
+
+impl_identity!(
+    bool,
+    char,
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    &'static str,
+    Duration,
+    Ordering,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    #[cfg(target_has_atomic = "8")]
+    atomic::AtomicBool,
+    #[cfg(target_has_atomic = "8")]
+    atomic::AtomicI8,
+    #[cfg(target_has_atomic = "8")]
+    atomic::AtomicU8,
+    #[cfg(target_has_atomic = "16")]
+    atomic::AtomicI16,
+    #[cfg(target_has_atomic = "16")]
+    atomic::AtomicU16,
+    #[cfg(target_has_atomic = "32")]
+    atomic::AtomicI32,
+    #[cfg(target_has_atomic = "32")]
+    atomic::AtomicU32,
+    #[cfg(target_has_atomic = "64")]
+    atomic::AtomicI64,
+    #[cfg(target_has_atomic = "64")]
+    atomic::AtomicU64,
+    #[cfg(target_has_atomic = "ptr")]
+    atomic::AtomicIsize,
+    #[cfg(target_has_atomic = "ptr")]
+    atomic::AtomicUsize,
+    #[cfg(feature = "alloc")]
+    String,
+);
+
 #[cfg(test)]
 #[cfg(feature = "alloc")]
 mod tests {
@@ -130,7 +584,6 @@ mod tests {
         assert_eq!(actual, None);
     }
 
-    #[cfg(feature = "unstable")]
     #[test]
     fn result_ok_cow() {
         let ok_borrowed: Result<Cow<str>, Cow<str>> = Ok(Cow::Borrowed("Elm"));
@@ -139,7 +592,6 @@ mod tests {
         assert_matches!(actual, Ok(Cow::Owned(_)));
     }
 
-    #[cfg(feature = "unstable")]
     #[test]
     fn result_err_cow() {
         let ok_borrowed: Result<Cow<str>, Cow<str>> = Err(Cow::Borrowed("Elm"));
@@ -147,4 +599,188 @@ mod tests {
         assert_eq!(actual, ok_borrowed);
         assert_matches!(actual, Err(Cow::Owned(_)));
     }
+
+    #[test]
+    fn vec_identity() {
+        let vec: Vec<(u32, String)> = alloc::vec![(1, "Elm".into())];
+        let actual = vec.clone().into_static();
+        assert_eq!(actual, vec);
+    }
+
+    #[test]
+    fn option_static_str() {
+        let option: Option<&'static str> = Some("Elm");
+        let actual = option.into_static();
+        assert_eq!(actual, Some("Elm"));
+    }
+
+    #[test]
+    fn one_tuple_cow() {
+        let tuple = (Cow::Borrowed("Elm"),);
+        let actual = tuple.clone().into_static();
+        assert_eq!(actual, tuple);
+        assert_matches!(actual, (Cow::Owned(_),));
+    }
+
+    #[test]
+    fn twelve_tuple() {
+        let tuple = (
+            0u8,
+            1u16,
+            2u32,
+            3u64,
+            4u128,
+            5usize,
+            6i8,
+            7i16,
+            8i32,
+            9i64,
+            10i128,
+            Cow::Borrowed("Elm"),
+        );
+        let actual = tuple.clone().into_static();
+        assert_eq!(actual, tuple);
+        assert_matches!(actual.11, Cow::Owned(_));
+    }
+
+    #[test]
+    fn range_cow() {
+        let range = Cow::Borrowed("Elm")..Cow::Borrowed("Oak");
+        let actual = range.clone().into_static();
+        assert_eq!(actual, range);
+        assert_matches!(actual.start, Cow::Owned(_));
+        assert_matches!(actual.end, Cow::Owned(_));
+    }
+
+    #[test]
+    fn bound_cow() {
+        let bound = Bound::Excluded(Cow::Borrowed("Elm"));
+        let actual = bound.clone().into_static();
+        assert_eq!(actual, bound);
+        assert_matches!(actual, Bound::Excluded(Cow::Owned(_)));
+    }
+
+    #[test]
+    fn phantom_data_unit_reference() {
+        fn into_static(phantom: PhantomData<&()>) -> PhantomData<&'static ()> {
+            phantom.into_static()
+        }
+
+        assert_eq!(into_static(PhantomData), PhantomData);
+    }
+
+    #[test]
+    fn ref_cell_cow() {
+        let cell = RefCell::new(Cow::Borrowed("Elm"));
+        let actual = cell.clone().into_static();
+        assert_eq!(actual, cell);
+        assert_matches!(*actual.borrow(), Cow::Owned(_));
+    }
+
+    #[test]
+    fn cell_option_static_str() {
+        let cell = Cell::new(Some("Elm"));
+        let actual = cell.into_static();
+        assert_eq!(actual.get(), Some("Elm"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn poisoned_mutex_cow() {
+        let mutex = Mutex::new(Cow::Borrowed("Elm"));
+        let _ = std::panic::catch_unwind(|| {
+            let _guard = mutex.lock().unwrap();
+            panic!("poison the mutex");
+        });
+        assert!(mutex.is_poisoned());
+        let actual = mutex.into_static();
+        assert!(!actual.is_poisoned());
+        assert_matches!(*actual.lock().unwrap(), Cow::Owned(ref elm) if elm == "Elm");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn rw_lock_cow() {
+        let lock = RwLock::new(Cow::Borrowed("Elm"));
+        let actual = lock.into_static();
+        assert_matches!(*actual.read().unwrap(), Cow::Owned(ref elm) if elm == "Elm");
+    }
+
+    #[test]
+    fn array_cow() {
+        let array = [Cow::Borrowed("Elm"), Cow::Borrowed("Oak")];
+        let actual = array.clone().into_static();
+        assert_eq!(actual, array);
+        assert_matches!(actual, [Cow::Owned(_), Cow::Owned(_)]);
+    }
+
+    #[test]
+    fn box_cow() {
+        let boxed = Box::new(Cow::Borrowed("Elm"));
+        let actual = boxed.clone().into_static();
+        assert_eq!(actual, boxed);
+        assert_matches!(*actual, Cow::Owned(_));
+    }
+
+    #[test]
+    fn boxed_slice_cow() {
+        let boxed: Box<[Cow<str>]> = alloc::vec![Cow::Borrowed("Elm"), Cow::Borrowed("Oak")].into();
+        let actual = boxed.clone().into_static();
+        assert_eq!(actual, boxed);
+        assert_matches!(actual[1], Cow::Owned(_));
+    }
+
+    #[test]
+    fn unique_rc_cow() {
+        let rc = Rc::new(Cow::Borrowed("Elm"));
+        let actual = rc.clone().into_static();
+        assert_eq!(actual, rc);
+        assert_matches!(*actual, Cow::Owned(_));
+    }
+
+    #[test]
+    fn shared_arc_cow() {
+        let arc = Arc::new(Cow::Borrowed("Elm"));
+        let shared = Arc::clone(&arc);
+        let actual = arc.into_static();
+        assert_eq!(actual, shared);
+        assert_matches!(*actual, Cow::Owned(_));
+    }
+
+    #[test]
+    fn btree_map_cow() {
+        let mut map = BTreeMap::new();
+        map.insert(Cow::Borrowed("Elm"), Cow::Borrowed("Tree"));
+        let actual = map.clone().into_static();
+        assert_eq!(actual, map);
+        assert_matches!(actual.iter().next(), Some((Cow::Owned(_), Cow::Owned(_))));
+    }
+
+    #[test]
+    fn vec_deque_cow() {
+        let deque: VecDeque<Cow<str>> = VecDeque::from(alloc::vec![Cow::Borrowed("Elm")]);
+        let actual = deque.clone().into_static();
+        assert_eq!(actual, deque);
+        assert_matches!(actual[0], Cow::Owned(_));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map_cow() {
+        let mut map = HashMap::new();
+        map.insert(Cow::Borrowed("Elm"), Cow::Borrowed("Tree"));
+        let actual = map.clone().into_static();
+        assert_eq!(actual, map);
+        assert_matches!(actual.iter().next(), Some((Cow::Owned(_), Cow::Owned(_))));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_set_cow() {
+        let mut set = HashSet::new();
+        set.insert(Cow::Borrowed("Elm"));
+        let actual = set.clone().into_static();
+        assert_eq!(actual, set);
+        assert_matches!(actual.iter().next(), Some(Cow::Owned(_)));
+    }
 }
@@ -13,6 +13,8 @@ to enable macros.
 lifetime = { version = "x.y", features = ["macros"] }
 ```
 
+The code generated by the macros requires Rust 1.78 or newer.
+
 # no_std
 
 Use the following in your `Cargo.toml`,
@@ -23,6 +25,15 @@ to disable the default `std` and `alloc` features.
 lifetime = { version = "x.y", default-features = false }
 ```
 
+The `alloc` feature can be enabled on its own
+to keep the impls for the types of the `alloc` crate,
+like `Cow`, `Vec` or `BTreeMap`.
+The `std` feature adds the impls for `HashMap` and `HashSet`.
+```toml
+[dependencies]
+lifetime = { version = "x.y", default-features = false, features = ["alloc"] }
+```
+
 */
 #![cfg_attr(
     feature = "alloc",
@@ -86,6 +97,8 @@ assert_eq!(static_xml_or_json.value, "xml or json");
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod into_static;
 mod to_borrowed;
@@ -95,3 +108,45 @@ pub use to_borrowed::ToBorrowed;
 
 #[cfg(feature = "macros")]
 pub use lifetime_proc_macros::{IntoStatic, ToBorrowed};
+
+/// Not public API, used by the code generated by the derive macros.
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod __private {
+    /// Copies a field which isn't converted, with an error naming the field if it isn't `Copy`.
+    #[diagnostic::on_unimplemented(
+        message = "the field of type `{Self}` can't be copied to the borrowed value",
+        label = "this field isn't `Copy`",
+        note = "add `#[lifetime(clone)]` to the field to clone it instead"
+    )]
+    pub trait CopyField: Copy {}
+
+    impl<T: Copy> CopyField for T {}
+
+    #[inline]
+    pub fn copy_field<T: CopyField>(field: &T) -> T {
+        *field
+    }
+
+    /// Implemented by `#[derive(IntoStatic)]` for a type with a `static` alias.
+    #[diagnostic::on_unimplemented(
+        message = "the `static` alias requires `{Self}` to derive `IntoStatic`",
+        label = "the `static` alias is generated by `#[derive(IntoStatic)]`",
+        note = "derive `IntoStatic` or remove the `static` alias"
+    )]
+    pub trait StaticAlias {}
+
+    /// Implemented by `#[derive(ToBorrowed)]` for a type with a `borrowed` alias.
+    #[diagnostic::on_unimplemented(
+        message = "the `borrowed` alias requires `{Self}` to derive `ToBorrowed`",
+        label = "the `borrowed` alias is generated by `#[derive(ToBorrowed)]`",
+        note = "derive `ToBorrowed` or remove the `borrowed` alias"
+    )]
+    pub trait BorrowedAlias {}
+
+    #[inline]
+    pub fn assert_static_alias<T: ?Sized + StaticAlias>() {}
+
+    #[inline]
+    pub fn assert_borrowed_alias<T: ?Sized + BorrowedAlias>() {}
+}
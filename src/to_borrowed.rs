@@ -1,5 +1,27 @@
 #[cfg(feature = "alloc")]
-use alloc::borrow::{Borrow, Cow, ToOwned};
+use alloc::{
+    borrow::{Borrow, Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+use core::{
+    cmp::{Ordering, Reverse},
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    },
+    ops::{Bound, Range, RangeFrom, RangeInclusive, RangeTo},
+    time::Duration,
+};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
 
 /// A trait for downgrading the lifetime of a type.
 ///
@@ -41,6 +63,19 @@ where
     }
 }
 
+/// Shortens the lifetime of a reference to the lifetime of the outer reference.
+impl<'c, T> ToBorrowed for &'c &T
+where
+    T: ?Sized,
+{
+    type Borrowed = &'c T;
+
+    #[inline]
+    fn to_borrowed(self) -> &'c T {
+        self
+    }
+}
+
 impl<'o, T> ToBorrowed for &'o Option<T>
 where
     for<'t> &'t T: ToBorrowed,
@@ -53,6 +88,419 @@ where
     }
 }
 
+impl<'r, T, E> ToBorrowed for &'r Result<T, E>
+where
+    for<'t> &'t T: ToBorrowed,
+    for<'t> &'t E: ToBorrowed,
+{
+    type Borrowed = Result<<&'r T as ToBorrowed>::Borrowed, <&'r E as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Self::Borrowed {
+        self.as_ref()
+            .map(ToBorrowed::to_borrowed)
+            .map_err(ToBorrowed::to_borrowed)
+    }
+}
+
+impl<'c, T, const N: usize> ToBorrowed for &'c [T; N]
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = [<&'c T as ToBorrowed>::Borrowed; N];
+
+    #[inline]
+    fn to_borrowed(self) -> [<&'c T as ToBorrowed>::Borrowed; N] {
+        core::array::from_fn(|i| self[i].to_borrowed())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, T> ToBorrowed for &'c Box<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = Box<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Box<<&'c T as ToBorrowed>::Borrowed> {
+        Box::new((**self).to_borrowed())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, T> ToBorrowed for &'c Box<[T]>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = Box<[<&'c T as ToBorrowed>::Borrowed]>;
+
+    #[inline]
+    fn to_borrowed(self) -> Box<[<&'c T as ToBorrowed>::Borrowed]> {
+        self.iter().map(ToBorrowed::to_borrowed).collect()
+    }
+}
+
+/// Allocates a new `Rc` for the borrowed value.
+#[cfg(feature = "alloc")]
+impl<'c, T> ToBorrowed for &'c Rc<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = Rc<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Rc<<&'c T as ToBorrowed>::Borrowed> {
+        Rc::new((**self).to_borrowed())
+    }
+}
+
+/// Allocates a new `Arc` for the borrowed value.
+#[cfg(feature = "alloc")]
+impl<'c, T> ToBorrowed for &'c Arc<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = Arc<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Arc<<&'c T as ToBorrowed>::Borrowed> {
+        Arc::new((**self).to_borrowed())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, T> ToBorrowed for &'c [T]
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = Vec<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Vec<<&'c T as ToBorrowed>::Borrowed> {
+        self.iter().map(ToBorrowed::to_borrowed).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, T> ToBorrowed for &'c Vec<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = Vec<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Vec<<&'c T as ToBorrowed>::Borrowed> {
+        self.iter().map(ToBorrowed::to_borrowed).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, T> ToBorrowed for &'c VecDeque<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = VecDeque<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> VecDeque<<&'c T as ToBorrowed>::Borrowed> {
+        self.iter().map(ToBorrowed::to_borrowed).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, T> ToBorrowed for &'c LinkedList<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = LinkedList<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> LinkedList<<&'c T as ToBorrowed>::Borrowed> {
+        self.iter().map(ToBorrowed::to_borrowed).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, T> ToBorrowed for &'c BinaryHeap<T>
+where
+    for<'t> &'t T: ToBorrowed,
+    <&'c T as ToBorrowed>::Borrowed: Ord,
+{
+    type Borrowed = BinaryHeap<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> BinaryHeap<<&'c T as ToBorrowed>::Borrowed> {
+        self.iter().map(ToBorrowed::to_borrowed).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, K, V> ToBorrowed for &'c BTreeMap<K, V>
+where
+    for<'t> &'t K: ToBorrowed,
+    for<'t> &'t V: ToBorrowed,
+    <&'c K as ToBorrowed>::Borrowed: Ord,
+{
+    type Borrowed = BTreeMap<<&'c K as ToBorrowed>::Borrowed, <&'c V as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Self::Borrowed {
+        self.iter()
+            .map(|(key, value)| (key.to_borrowed(), value.to_borrowed()))
+            .collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, T> ToBorrowed for &'c BTreeSet<T>
+where
+    for<'t> &'t T: ToBorrowed,
+    <&'c T as ToBorrowed>::Borrowed: Ord,
+{
+    type Borrowed = BTreeSet<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> BTreeSet<<&'c T as ToBorrowed>::Borrowed> {
+        self.iter().map(ToBorrowed::to_borrowed).collect()
+    }
+}
+
+/// The hasher of the map is cloned into the new map.
+#[cfg(feature = "std")]
+impl<'c, K, V, S> ToBorrowed for &'c HashMap<K, V, S>
+where
+    for<'t> &'t K: ToBorrowed,
+    for<'t> &'t V: ToBorrowed,
+    <&'c K as ToBorrowed>::Borrowed: Eq + Hash,
+    S: BuildHasher + Clone,
+{
+    type Borrowed = HashMap<<&'c K as ToBorrowed>::Borrowed, <&'c V as ToBorrowed>::Borrowed, S>;
+
+    #[inline]
+    fn to_borrowed(self) -> Self::Borrowed {
+        let mut map = HashMap::with_capacity_and_hasher(self.len(), self.hasher().clone());
+        map.extend(
+            self.iter()
+                .map(|(key, value)| (key.to_borrowed(), value.to_borrowed())),
+        );
+        map
+    }
+}
+
+/// The hasher of the set is cloned into the new set.
+#[cfg(feature = "std")]
+impl<'c, T, S> ToBorrowed for &'c HashSet<T, S>
+where
+    for<'t> &'t T: ToBorrowed,
+    <&'c T as ToBorrowed>::Borrowed: Eq + Hash,
+    S: BuildHasher + Clone,
+{
+    type Borrowed = HashSet<<&'c T as ToBorrowed>::Borrowed, S>;
+
+    #[inline]
+    fn to_borrowed(self) -> HashSet<<&'c T as ToBorrowed>::Borrowed, S> {
+        let mut set = HashSet::with_capacity_and_hasher(self.len(), self.hasher().clone());
+        set.extend(self.iter().map(ToBorrowed::to_borrowed));
+        set
+    }
+}
+
+impl<'c, T> ToBorrowed for &'c Range<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = Range<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Range<<&'c T as ToBorrowed>::Borrowed> {
+        self.start.to_borrowed()..self.end.to_borrowed()
+    }
+}
+
+impl<'c, T> ToBorrowed for &'c RangeInclusive<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = RangeInclusive<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> RangeInclusive<<&'c T as ToBorrowed>::Borrowed> {
+        self.start().to_borrowed()..=self.end().to_borrowed()
+    }
+}
+
+impl<'c, T> ToBorrowed for &'c RangeFrom<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = RangeFrom<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> RangeFrom<<&'c T as ToBorrowed>::Borrowed> {
+        self.start.to_borrowed()..
+    }
+}
+
+impl<'c, T> ToBorrowed for &'c RangeTo<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = RangeTo<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> RangeTo<<&'c T as ToBorrowed>::Borrowed> {
+        ..self.end.to_borrowed()
+    }
+}
+
+impl<'c, T> ToBorrowed for &'c Bound<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = Bound<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Bound<<&'c T as ToBorrowed>::Borrowed> {
+        match self {
+            Bound::Included(value) => Bound::Included(value.to_borrowed()),
+            Bound::Excluded(value) => Bound::Excluded(value.to_borrowed()),
+            Bound::Unbounded => Bound::Unbounded,
+        }
+    }
+}
+
+impl<'c, T> ToBorrowed for &'c Reverse<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = Reverse<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Reverse<<&'c T as ToBorrowed>::Borrowed> {
+        Reverse(self.0.to_borrowed())
+    }
+}
+
+impl<'c, T> ToBorrowed for &'c Wrapping<T>
+where
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = Wrapping<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> Wrapping<<&'c T as ToBorrowed>::Borrowed> {
+        Wrapping(self.0.to_borrowed())
+    }
+}
+
+impl<'c, T> ToBorrowed for &'c PhantomData<T>
+where
+    T: ?Sized,
+    for<'t> &'t T: ToBorrowed,
+{
+    type Borrowed = PhantomData<<&'c T as ToBorrowed>::Borrowed>;
+
+    #[inline]
+    fn to_borrowed(self) -> PhantomData<<&'c T as ToBorrowed>::Borrowed> {
+        PhantomData
+    }
+}
+
+/// Implements `ToBorrowed` for references to `Copy` types without lifetimes
+/// by copying the value.
+macro_rules! impl_copy {
+    ($($T:ty),+ $(,)?) => {
+        $(
+            impl<'c> ToBorrowed for &'c $T {
+                type Borrowed = $T;
+
+                #[inline]
+                fn to_borrowed(self) -> $T {
+                    *self
+                }
+            }
+        )+
+    };
+}
+
+impl_copy!(
+    bool,
+    char,
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    Duration,
+    Ordering,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+);
+
+/// Clones the `String`, so that the borrowed value has the same type as the
+/// original one. Borrowing it as `&str` would change the type of the
+/// containers holding it.
+#[cfg(feature = "alloc")]
+impl ToBorrowed for &String {
+    type Borrowed = String;
+
+    #[inline]
+    fn to_borrowed(self) -> String {
+        self.clone()
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($T:ident),*) => {
+        impl<'c, $($T),*> ToBorrowed for &'c ($($T,)*)
+        where
+            $(for<'t> &'t $T: ToBorrowed),*
+        {
+            type Borrowed = ($(<&'c $T as ToBorrowed>::Borrowed,)*);
+
+            #[allow(non_snake_case, clippy::unused_unit)]
+            fn to_borrowed(self) -> Self::Borrowed {
+                let ($($T,)*) = self;
+                ($($T.to_borrowed(),)*)
+            }
+        }
+    };
+}
+
+impl_tuple!();
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+impl_tuple!(A, B, C, D, E, F, G);
+impl_tuple!(A, B, C, D, E, F, G, H);
+impl_tuple!(A, B, C, D, E, F, G, H, I);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
 #[cfg(feature = "alloc")]
 #[cfg(test)]
 mod tests {
@@ -73,4 +521,158 @@ mod tests {
         let actual = option.to_borrowed();
         assert_eq!(actual, None);
     }
+
+    #[test]
+    fn vec_copy() {
+        let vec: Vec<u32> = alloc::vec![1, 2];
+        let actual: Vec<u32> = vec.to_borrowed();
+        assert_eq!(actual, vec);
+    }
+
+    #[test]
+    fn reference_str() {
+        let reference: &str = "Elm";
+        let actual: &str = (&reference).to_borrowed();
+        assert_eq!(actual, "Elm");
+    }
+
+    #[test]
+    fn option_reference_slice() {
+        let option: Option<&[u8]> = Some(b"Elm");
+        let actual: Option<&[u8]> = option.to_borrowed();
+        assert_eq!(actual, option);
+    }
+
+    #[test]
+    fn range_inclusive_cow() {
+        let range: RangeInclusive<Cow<str>> = Cow::Owned("Elm".into())..=Cow::Owned("Oak".into());
+        let actual = range.to_borrowed();
+        assert_eq!(actual, range);
+        assert_matches!(actual.start(), Cow::Borrowed(_));
+        assert_matches!(actual.end(), Cow::Borrowed(_));
+    }
+
+    #[test]
+    fn phantom_data_reference() {
+        fn to_borrowed<'r>(phantom: &'r PhantomData<&str>) -> PhantomData<&'r str> {
+            phantom.to_borrowed()
+        }
+
+        assert_eq!(to_borrowed(&PhantomData), PhantomData);
+    }
+
+    #[test]
+    fn tuple_cow() {
+        let tuple: (u32, Cow<str>) = (1, Cow::Owned("Elm".into()));
+        let actual = tuple.to_borrowed();
+        assert_eq!(actual, tuple);
+        assert_matches!(actual, (1, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn unit() {
+        let actual: () = ().to_borrowed();
+        assert_eq!(actual, ());
+    }
+
+    #[test]
+    fn array_cow() {
+        let array: [Cow<str>; 2] = [Cow::Owned("Elm".into()), Cow::Owned("Oak".into())];
+        let actual = array.to_borrowed();
+        assert_eq!(actual, array);
+        assert_matches!(actual, [Cow::Borrowed(_), Cow::Borrowed(_)]);
+    }
+
+    #[test]
+    fn result_ok_cow() {
+        let ok_owned: Result<Cow<str>, Cow<str>> = Ok(Cow::Owned("Elm".into()));
+        let actual = ok_owned.to_borrowed();
+        assert_eq!(actual, ok_owned);
+        assert_matches!(actual, Ok(Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn result_err_cow() {
+        let err_owned: Result<Cow<str>, Cow<str>> = Err(Cow::Owned("Elm".into()));
+        let actual = err_owned.to_borrowed();
+        assert_eq!(actual, err_owned);
+        assert_matches!(actual, Err(Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn vec_string() {
+        let vec: Vec<String> = alloc::vec!["Elm".into()];
+        let actual: Vec<String> = vec.to_borrowed();
+        assert_eq!(actual, vec);
+    }
+
+    #[test]
+    fn box_cow() {
+        let boxed: Box<Cow<str>> = Box::new(Cow::Owned("Elm".into()));
+        let actual = boxed.to_borrowed();
+        assert_eq!(actual, boxed);
+        assert_matches!(*actual, Cow::Borrowed(_));
+    }
+
+    #[test]
+    fn boxed_slice_cow() {
+        let boxed: Box<[Cow<str>]> = Box::new([Cow::Owned("Elm".into())]);
+        let actual = boxed.to_borrowed();
+        assert_eq!(actual, boxed);
+        assert_matches!(actual[0], Cow::Borrowed(_));
+    }
+
+    #[test]
+    fn rc_cow() {
+        let rc: Rc<Cow<str>> = Rc::new(Cow::Owned("Elm".into()));
+        let actual = rc.to_borrowed();
+        assert_eq!(actual, rc);
+        assert_matches!(*actual, Cow::Borrowed(_));
+    }
+
+    #[test]
+    fn arc_cow() {
+        let arc: Arc<Cow<str>> = Arc::new(Cow::Owned("Elm".into()));
+        let actual = arc.to_borrowed();
+        assert_eq!(actual, arc);
+        assert_matches!(*actual, Cow::Borrowed(_));
+    }
+
+    #[test]
+    fn vec_cow() {
+        let vec: Vec<Cow<str>> = alloc::vec![Cow::Owned("Elm".into())];
+        let actual = vec.to_borrowed();
+        assert_eq!(actual, vec);
+        assert_matches!(actual[0], Cow::Borrowed(_));
+    }
+
+    #[test]
+    fn slice_cow() {
+        let slice: &[Cow<str>] = &[Cow::Owned("Elm".into())];
+        let actual = slice.to_borrowed();
+        assert_eq!(actual, slice);
+        assert_matches!(actual[0], Cow::Borrowed(_));
+    }
+
+    #[test]
+    fn btree_map_cow() {
+        let mut map: BTreeMap<Cow<str>, Cow<str>> = BTreeMap::new();
+        map.insert(Cow::Owned("Elm".into()), Cow::Owned("Tree".into()));
+        let actual = map.to_borrowed();
+        assert_eq!(actual, map);
+        assert_matches!(
+            actual.iter().next(),
+            Some((Cow::Borrowed(_), Cow::Borrowed(_)))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_set_cow() {
+        let mut set: HashSet<Cow<str>> = HashSet::new();
+        set.insert(Cow::Owned("Elm".into()));
+        let actual = set.to_borrowed();
+        assert_eq!(actual, set);
+        assert_matches!(actual.iter().next(), Some(Cow::Borrowed(_)));
+    }
 }
@@ -1,5 +1,5 @@
 use lifetime::IntoStatic;
-use std::{borrow::Cow, panic::Location};
+use std::{borrow::Cow, fmt::Debug, panic::Location};
 
 #[test]
 fn primitive_and_cow_str_struct() {
@@ -82,3 +82,576 @@ fn derive_struct_with_static_reference() {
     let borrowed = into_static(example.clone());
     assert_eq!(example, borrowed);
 }
+
+#[test]
+fn recursive_boxed_struct() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Node<'a> {
+        name: Cow<'a, str>,
+        next: Option<Box<Node<'a>>>,
+    }
+
+    fn into_static<'a>(x: Node<'a>) -> Node<'static> {
+        x.into_static()
+    }
+
+    let example = Node {
+        name: Cow::Borrowed("root"),
+        next: Some(Box::new(Node {
+            name: Cow::Borrowed("leaf"),
+            next: None,
+        })),
+    };
+    let borrowed = into_static(example.clone());
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn core_wrapper_fields() {
+    use std::{marker::PhantomData, ops::Range};
+
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Example<'a> {
+        range: Range<Cow<'a, str>>,
+        marker: PhantomData<&'a ()>,
+    }
+
+    fn into_static<'a>(x: Example<'a>) -> Example<'static> {
+        x.into_static()
+    }
+
+    let example = Example {
+        range: Cow::Borrowed("a")..Cow::Borrowed("z"),
+        marker: PhantomData,
+    };
+    let borrowed = into_static(example.clone());
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn generic_type_param() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Page<'a, T>
+    where
+        T: Clone,
+    {
+        items: Vec<T>,
+        title: Cow<'a, str>,
+    }
+
+    fn into_static<'a, 'b>(x: Page<'a, Cow<'b, str>>) -> Page<'static, Cow<'static, str>> {
+        x.into_static()
+    }
+
+    let example = Page {
+        items: vec![Cow::Borrowed("Elm")],
+        title: Cow::Borrowed("Trees"),
+    };
+    let borrowed = into_static(example.clone());
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn generic_enum() {
+    #[allow(dead_code)]
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    enum Example<T> {
+        Value(T),
+        Number { number: usize },
+    }
+
+    fn into_static<'a>(x: Example<Cow<'a, str>>) -> Example<Cow<'static, str>> {
+        x.into_static()
+    }
+
+    let example = Example::Value(Cow::Borrowed("Elm"));
+    let borrowed = into_static(example.clone());
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn generic_const_param() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Buf<'a, const N: usize> {
+        bytes: [u8; N],
+        names: [Cow<'a, str>; N],
+    }
+
+    fn into_static<'a, const N: usize>(x: Buf<'a, N>) -> Buf<'static, N> {
+        x.into_static()
+    }
+
+    let example = Buf {
+        bytes: [1, 2],
+        names: [Cow::Borrowed("Elm"), Cow::Borrowed("Oak")],
+    };
+    let borrowed = into_static(example.clone());
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn lifetime_bounds() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Section<'a, 'b>
+    where
+        'b: 'a,
+    {
+        title: Cow<'a, str>,
+        body: Cow<'b, str>,
+    }
+
+    fn into_static<'a, 'b: 'a>(x: Section<'a, 'b>) -> Section<'static, 'static> {
+        x.into_static()
+    }
+
+    let example = Section {
+        title: Cow::Borrowed("Elm"),
+        body: Cow::Borrowed("Tree"),
+    };
+    let borrowed = into_static(example.clone());
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn unit_variants() {
+    #[allow(dead_code)]
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    enum Token<'a> {
+        Eof,
+        Empty {},
+        Ident(Cow<'a, str>),
+    }
+
+    fn into_static<'a>(x: Token<'a>) -> Token<'static> {
+        x.into_static()
+    }
+
+    let example = Token::Eof;
+    let borrowed = into_static(example.clone());
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn unit_structs() {
+    use std::marker::PhantomData;
+
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Unit;
+
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Marker<'a>(PhantomData<&'a ()>);
+
+    fn into_static<'a>(x: Marker<'a>) -> Marker<'static> {
+        x.into_static()
+    }
+
+    assert_eq!(Unit.into_static(), Unit);
+    let example = Marker(PhantomData);
+    let borrowed = into_static(example.clone());
+    assert_eq!(example, borrowed);
+}
+
+mod foreign {
+    use std::borrow::Cow;
+
+    /// A borrowed type of another crate, which doesn't implement the lifetime traits.
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct Name<'a>(pub Cow<'a, str>);
+
+    #[derive(Debug, PartialEq, Clone)]
+    pub enum Token<'a> {
+        Word(Cow<'a, str>),
+        Number(u64),
+    }
+
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct Span<'a> {
+        pub source: Cow<'a, str>,
+        pub start: usize,
+    }
+
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct Spanned<'a, T> {
+        pub value: T,
+        pub source: Cow<'a, str>,
+    }
+}
+
+mod foreign_name {
+    use super::foreign::Name;
+    use std::borrow::Cow;
+
+    pub fn into_static(name: Name<'_>) -> Name<'static> {
+        Name(Cow::Owned(name.0.into_owned()))
+    }
+}
+
+#[test]
+fn field_attributes() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Example<'a> {
+        #[lifetime(with = "foreign_name")]
+        name: foreign::Name<'a>,
+        #[lifetime(into_static_with = "foreign_name::into_static")]
+        alias: foreign::Name<'a>,
+        #[lifetime(default)]
+        cache: Option<Cow<'a, str>>,
+        #[lifetime(clone)]
+        id: String,
+    }
+
+    #[allow(dead_code)]
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    enum Cached<'a> {
+        Value {
+            value: Cow<'a, str>,
+            #[lifetime(skip)]
+            cache: Option<Cow<'a, str>>,
+        },
+    }
+
+    fn into_static<'a>(x: Example<'a>) -> Example<'static> {
+        x.into_static()
+    }
+
+    let example = Example {
+        name: foreign::Name(Cow::Borrowed("Elm")),
+        alias: foreign::Name(Cow::Borrowed("Oak")),
+        cache: Some(Cow::Borrowed("cached")),
+        id: "tree".into(),
+    };
+    let borrowed = into_static(example.clone());
+    assert_eq!(borrowed.name, example.name);
+    assert_eq!(borrowed.alias, example.alias);
+    assert_eq!(borrowed.cache, None);
+    assert_eq!(borrowed.id, example.id);
+
+    let cached = Cached::Value {
+        value: Cow::Borrowed("Elm"),
+        cache: Some(Cow::Borrowed("cached")),
+    };
+    assert_eq!(
+        cached.into_static(),
+        Cached::Value {
+            value: Cow::Borrowed("Elm"),
+            cache: None,
+        }
+    );
+}
+
+macro_rules! cow_str {
+    ($lifetime:lifetime) => {
+        Cow<$lifetime, str>
+    };
+}
+
+#[test]
+fn conversion_attributes() {
+    #[derive(IntoStatic, Clone)]
+    struct Example<'a> {
+        #[lifetime(convert)]
+        name: cow_str!('a),
+        #[lifetime(copy)]
+        callback: fn(&'a str) -> usize,
+    }
+
+    fn into_static<'a>(x: Example<'a>) -> Example<'static> {
+        x.into_static()
+    }
+
+    let example = Example {
+        name: Cow::Borrowed("Elm"),
+        callback: str::len,
+    };
+    let converted = into_static(example.clone());
+    assert_eq!(converted.name, example.name);
+    assert_eq!((converted.callback)("Oak"), 3);
+}
+
+#[test]
+fn higher_ranked_function_pointer() {
+    #[derive(IntoStatic, Clone)]
+    struct Example<'a> {
+        callback: for<'x> fn(&'x str) -> usize,
+        name: Cow<'a, str>,
+    }
+
+    fn into_static<'a>(x: Example<'a>) -> Example<'static> {
+        x.into_static()
+    }
+
+    let example = Example {
+        callback: str::len,
+        name: Cow::Borrowed("Elm"),
+    };
+    let converted = into_static(example.clone());
+    assert_eq!(converted.name, example.name);
+    assert_eq!((converted.callback)("Oak"), 3);
+}
+
+#[test]
+fn cfg_attributes() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Example<'a> {
+        #[cfg(not(test))]
+        disabled: Cow<'a, str>,
+        #[cfg(test)]
+        enabled: Cow<'a, str>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    enum Cached<'a> {
+        #[cfg(not(test))]
+        Disabled(Cow<'a, str>),
+        Value {
+            #[cfg(not(test))]
+            disabled: Cow<'a, str>,
+            value: Cow<'a, str>,
+        },
+    }
+
+    fn into_static<'a>(x: Example<'a>) -> Example<'static> {
+        x.into_static()
+    }
+
+    let example = Example {
+        enabled: Cow::Borrowed("Elm"),
+    };
+    assert_eq!(into_static(example.clone()), example);
+
+    let cached = Cached::Value {
+        value: Cow::Borrowed("Elm"),
+    };
+    assert_eq!(cached.clone().into_static(), cached);
+}
+
+#[allow(dead_code)]
+#[derive(IntoStatic)]
+#[lifetime(remote = "foreign::Token")]
+enum TokenDef<'a> {
+    Word(Cow<'a, str>),
+    Number(u64),
+}
+
+#[allow(dead_code)]
+#[derive(IntoStatic)]
+#[lifetime(remote = "foreign::Span")]
+struct SpanDef<'a> {
+    source: Cow<'a, str>,
+    start: usize,
+}
+
+#[test]
+fn remote() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Example<'a> {
+        #[lifetime(with = "TokenDef")]
+        token: foreign::Token<'a>,
+        #[lifetime(with = "SpanDef")]
+        span: foreign::Span<'a>,
+    }
+
+    fn into_static<'a>(x: Example<'a>) -> Example<'static> {
+        x.into_static()
+    }
+
+    let example = Example {
+        token: foreign::Token::Word(Cow::Borrowed("Elm")),
+        span: foreign::Span {
+            source: Cow::Borrowed("Elm Oak"),
+            start: 4,
+        },
+    };
+    assert_eq!(into_static(example.clone()), example);
+    assert_eq!(
+        TokenDef::into_static(foreign::Token::Number(1)),
+        foreign::Token::Number(1)
+    );
+}
+
+#[allow(dead_code)]
+#[derive(IntoStatic)]
+#[lifetime(remote = "foreign::Spanned")]
+struct SpannedDef<'a, T> {
+    value: T,
+    source: Cow<'a, str>,
+}
+
+#[test]
+fn remote_generic() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    struct Example<'a> {
+        #[lifetime(with = "SpannedDef")]
+        word: foreign::Spanned<'a, Cow<'a, str>>,
+    }
+
+    fn into_static<'a>(x: Example<'a>) -> Example<'static> {
+        x.into_static()
+    }
+
+    let word = foreign::Spanned {
+        value: Cow::Borrowed("Elm"),
+        source: Cow::Borrowed("Elm Oak"),
+    };
+    assert_eq!(SpannedDef::into_static(word.clone()), word);
+    let example = Example { word };
+    assert_eq!(into_static(example.clone()), example);
+}
+
+#[test]
+fn bound_attributes() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    #[lifetime(into_static_bound = "T: IntoStatic, T::Static: Send")]
+    struct Example<'a, T> {
+        items: Vec<T>,
+        name: Cow<'a, str>,
+    }
+
+    fn into_static_send<'a, T>(x: Example<'a, T>) -> impl Send
+    where
+        T: IntoStatic,
+        T::Static: Send,
+    {
+        x.into_static()
+    }
+
+    let example = Example {
+        items: vec![Cow::Borrowed("Elm")],
+        name: Cow::Borrowed("Oak"),
+    };
+    into_static_send(example.clone());
+    assert_eq!(example.clone().into_static(), example);
+}
+
+#[test]
+fn bound_attributes_with_bounded_type() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    #[lifetime(into_static_bound = "T: IntoStatic")]
+    struct Example<'a, T: Clone>
+    where
+        T: Debug,
+    {
+        items: Vec<T>,
+        name: Cow<'a, str>,
+    }
+
+    let example = Example {
+        items: vec![Cow::Borrowed("Elm")],
+        name: Cow::Borrowed("Oak"),
+    };
+    assert_eq!(example.clone().into_static(), example);
+}
+
+#[test]
+fn kept_lifetimes() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    #[lifetime(keep = "'arena")]
+    struct Node<'arena, 'src> {
+        name: Cow<'src, str>,
+        children: &'arena [u32],
+    }
+
+    fn into_static<'arena>(x: Node<'arena, '_>) -> Node<'arena, 'static> {
+        x.into_static()
+    }
+
+    let arena = vec![1, 2];
+    let node = Node {
+        name: Cow::Borrowed("Elm"),
+        children: &arena,
+    };
+    assert_eq!(into_static(node.clone()), node);
+}
+
+#[test]
+fn type_alias() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    #[lifetime(alias(static = "HeaderOwned"))]
+    struct Header<'a, T> {
+        name: Cow<'a, str>,
+        values: Vec<T>,
+    }
+
+    fn into_static<T: IntoStatic>(x: Header<'_, T>) -> HeaderOwned<T> {
+        x.into_static()
+    }
+
+    let header = Header {
+        name: Cow::Borrowed("content"),
+        values: vec![Cow::Borrowed("xml")],
+    };
+    assert_eq!(into_static(header.clone()), header);
+}
+
+#[test]
+fn type_aliases_of_both_derives() {
+    #[derive(IntoStatic, lifetime::ToBorrowed, Debug, PartialEq, Clone)]
+    #[lifetime(alias(static = "HeaderOwned", borrowed = "HeaderRef"))]
+    struct Header<'a, T> {
+        name: Cow<'a, str>,
+        values: Vec<T>,
+    }
+
+    fn into_static<T: IntoStatic>(x: Header<'_, T>) -> HeaderOwned<T> {
+        x.into_static()
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Header<'a, Cow<'a, str>>) -> HeaderRef<'r, Cow<'a, str>> {
+        lifetime::ToBorrowed::to_borrowed(x)
+    }
+
+    let header = Header {
+        name: Cow::Borrowed("content"),
+        values: vec![Cow::Borrowed("xml")],
+    };
+    assert_eq!(to_borrowed(&header), header);
+    assert_eq!(into_static(header.clone()), header);
+}
+
+#[test]
+fn from_attribute() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    #[lifetime(from)]
+    struct Header<'a> {
+        name: Cow<'a, str>,
+    }
+
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    #[lifetime(from)]
+    struct Headers<'a, T: Clone> {
+        name: Cow<'a, str>,
+        values: Vec<T>,
+    }
+
+    let header = Header {
+        name: Cow::Borrowed("content"),
+    };
+    assert_eq!(Header::<'static>::from_borrowed(header.clone()), header);
+
+    let headers = Headers {
+        name: Cow::Borrowed("content"),
+        values: vec![Cow::Borrowed("xml")],
+    };
+    let static_headers: Headers<'static, Cow<'static, str>> =
+        Headers::from_borrowed(headers.clone());
+    assert_eq!(static_headers, headers);
+}
+
+mod facade {
+    pub use lifetime;
+}
+
+#[test]
+fn crate_path() {
+    #[derive(IntoStatic, Debug, PartialEq, Clone)]
+    #[lifetime(crate = "facade::lifetime")]
+    struct Example<'a>(Cow<'a, str>);
+
+    fn into_static<'a>(x: Example<'a>) -> Example<'static> {
+        x.into_static()
+    }
+
+    let example = Example(Cow::Borrowed("Elm"));
+    assert_eq!(into_static(example.clone()), example);
+}
@@ -1,5 +1,5 @@
 use lifetime::ToBorrowed;
-use std::{borrow::Cow, panic::Location};
+use std::{borrow::Cow, fmt::Debug, panic::Location};
 
 #[test]
 fn primitive_and_cow_str_struct() {
@@ -82,3 +82,640 @@ fn derive_struct_with_static_reference() {
     let borrowed = to_borrowed(&example);
     assert_eq!(example, borrowed);
 }
+
+#[test]
+fn recursive_boxed_struct() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Node<'a> {
+        name: Cow<'a, str>,
+        next: Option<Box<Node<'a>>>,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Node<'a>) -> Node<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Node {
+        name: Cow::Owned("root".into()),
+        next: Some(Box::new(Node {
+            name: Cow::Owned("leaf".into()),
+            next: None,
+        })),
+    };
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn vec_field() {
+    #[derive(ToBorrowed, Debug, Default, PartialEq)]
+    struct Example<'a> {
+        cows: Vec<Cow<'a, str>>,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Example {
+        cows: vec![Cow::Owned("Elm".into())],
+    };
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn reference_fields() {
+    #[derive(ToBorrowed, Debug, Default, PartialEq)]
+    struct Example<'a> {
+        name: &'a str,
+        bytes: &'a [u8],
+        cow: Cow<'a, str>,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Example {
+        name: "Elm",
+        bytes: b"Elm",
+        cow: Cow::Owned("Elm".into()),
+    };
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn reference_variants() {
+    #[allow(dead_code)]
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    enum Example<'a> {
+        Str { name: &'a str },
+        Bytes(&'a [u8]),
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Example::Bytes(b"Elm");
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn core_wrapper_fields() {
+    use std::{marker::PhantomData, ops::Bound};
+
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Example<'a> {
+        bound: Bound<Cow<'a, str>>,
+        marker: PhantomData<&'a ()>,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Example {
+        bound: Bound::Included(Cow::Owned("a".into())),
+        marker: PhantomData,
+    };
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn generic_type_param() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Page<'a, T>
+    where
+        T: Clone,
+    {
+        items: Vec<T>,
+        title: Cow<'a, str>,
+    }
+
+    fn to_borrowed<'r, 'a, 'b>(x: &'r Page<'a, Cow<'b, str>>) -> Page<'r, Cow<'r, str>> {
+        x.to_borrowed()
+    }
+
+    let example = Page {
+        items: vec![Cow::Owned("Elm".into())],
+        title: Cow::Owned("Trees".into()),
+    };
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn generic_enum() {
+    #[allow(dead_code)]
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    enum Example<T> {
+        Value(T),
+        Number { number: usize },
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<Cow<'a, str>>) -> Example<Cow<'r, str>> {
+        x.to_borrowed()
+    }
+
+    let example = Example::Value(Cow::Owned("Elm".into()));
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn generic_const_param() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Buf<'a, const N: usize> {
+        bytes: [u8; N],
+        names: [Cow<'a, str>; N],
+    }
+
+    fn to_borrowed<'r, 'a, const N: usize>(x: &'r Buf<'a, N>) -> Buf<'r, N> {
+        x.to_borrowed()
+    }
+
+    let example = Buf {
+        bytes: [1, 2],
+        names: [Cow::Owned("Elm".into()), Cow::Owned("Oak".into())],
+    };
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn lifetime_bounds() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Doc<'a, 'b: 'a> {
+        title: &'a Cow<'b, str>,
+        body: Cow<'b, str>,
+    }
+
+    fn to_borrowed<'r, 'a, 'b: 'a>(x: &'r Doc<'a, 'b>) -> Doc<'r, 'r> {
+        x.to_borrowed()
+    }
+
+    let title = Cow::Owned("Elm".into());
+    let example = Doc {
+        title: &title,
+        body: Cow::Owned("Tree".into()),
+    };
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn unit_variants() {
+    #[allow(dead_code)]
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    enum Token<'a> {
+        Eof,
+        Empty {},
+        Ident(Cow<'a, str>),
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Token<'a>) -> Token<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Token::Eof;
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+#[test]
+fn unit_structs() {
+    use std::marker::PhantomData;
+
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Unit;
+
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Marker<'a>(PhantomData<&'a ()>);
+
+    fn to_borrowed<'r, 'a>(x: &'r Marker<'a>) -> Marker<'r> {
+        x.to_borrowed()
+    }
+
+    assert_eq!(Unit.to_borrowed(), Unit);
+    let example = Marker(PhantomData);
+    let borrowed = to_borrowed(&example);
+    assert_eq!(example, borrowed);
+}
+
+mod foreign {
+    use std::borrow::Cow;
+
+    /// A borrowed type of another crate, which doesn't implement the lifetime traits.
+    #[derive(Debug, PartialEq)]
+    pub struct Name<'a>(pub &'a str);
+
+    #[derive(Debug, PartialEq)]
+    pub enum Token<'a> {
+        Word(Cow<'a, str>),
+        Number(u64),
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Span<'a> {
+        pub source: Cow<'a, str>,
+        pub start: usize,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Spans<'a, const N: usize> {
+        pub sources: [Cow<'a, str>; N],
+    }
+}
+
+mod foreign_name {
+    use super::foreign::Name;
+
+    pub fn to_borrowed<'r>(name: &'r Name<'_>) -> Name<'r> {
+        Name(name.0)
+    }
+}
+
+#[test]
+fn field_attributes() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Example<'a> {
+        #[lifetime(to_borrowed_with = "foreign_name::to_borrowed")]
+        name: foreign::Name<'a>,
+        #[lifetime(default)]
+        cache: Option<Cow<'a, str>>,
+        #[lifetime(clone)]
+        id: String,
+    }
+
+    #[allow(dead_code)]
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    enum Cached<'a> {
+        Value {
+            #[lifetime(with = "foreign_name")]
+            name: foreign::Name<'a>,
+            #[lifetime(skip)]
+            cache: Option<Cow<'a, str>>,
+        },
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Example {
+        name: foreign::Name("Elm"),
+        cache: Some(Cow::Borrowed("cached")),
+        id: "tree".into(),
+    };
+    let borrowed = to_borrowed(&example);
+    assert_eq!(borrowed.name, example.name);
+    assert_eq!(borrowed.cache, None);
+    assert_eq!(borrowed.id, example.id);
+
+    let cached = Cached::Value {
+        name: foreign::Name("Elm"),
+        cache: Some(Cow::Borrowed("cached")),
+    };
+    assert_eq!(
+        cached.to_borrowed(),
+        Cached::Value {
+            name: foreign::Name("Elm"),
+            cache: None,
+        }
+    );
+}
+
+macro_rules! cow_str {
+    ($lifetime:lifetime) => {
+        Cow<$lifetime, str>
+    };
+}
+
+#[test]
+fn conversion_attributes() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Example<'a> {
+        #[lifetime(convert)]
+        name: cow_str!('a),
+        #[lifetime(copy)]
+        text: &'a str,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Example {
+        name: Cow::Owned("Elm".into()),
+        text: "Oak",
+    };
+    assert_eq!(to_borrowed(&example), example);
+}
+
+#[test]
+fn higher_ranked_function_pointer() {
+    #[derive(ToBorrowed)]
+    struct Example<'a> {
+        callback: for<'x> fn(&'x str) -> usize,
+        name: Cow<'a, str>,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Example {
+        callback: str::len,
+        name: Cow::Owned("Elm".into()),
+    };
+    let borrowed = to_borrowed(&example);
+    assert_eq!(borrowed.name, example.name);
+    assert_eq!((borrowed.callback)("Oak"), 3);
+}
+
+#[test]
+fn cfg_attributes() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Example<'a> {
+        #[cfg(not(test))]
+        disabled: Cow<'a, str>,
+        #[cfg(test)]
+        enabled: Cow<'a, str>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    enum Cached<'a> {
+        #[cfg(not(test))]
+        Disabled(Cow<'a, str>),
+        Value {
+            #[cfg(not(test))]
+            disabled: Cow<'a, str>,
+            value: Cow<'a, str>,
+        },
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Example {
+        enabled: Cow::Owned("Elm".into()),
+    };
+    assert_eq!(to_borrowed(&example), example);
+
+    let cached = Cached::Value {
+        value: Cow::Owned("Elm".into()),
+    };
+    assert_eq!(cached.to_borrowed(), cached);
+}
+
+#[allow(dead_code)]
+#[derive(ToBorrowed)]
+#[lifetime(remote = "foreign::Token")]
+enum TokenDef<'a> {
+    Word(Cow<'a, str>),
+    Number(u64),
+}
+
+#[allow(dead_code)]
+#[derive(ToBorrowed)]
+#[lifetime(remote = "foreign::Span")]
+struct SpanDef<'a> {
+    source: Cow<'a, str>,
+    start: usize,
+}
+
+#[test]
+fn remote() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Example<'a> {
+        #[lifetime(with = "TokenDef")]
+        token: foreign::Token<'a>,
+        #[lifetime(with = "SpanDef")]
+        span: foreign::Span<'a>,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Example {
+        token: foreign::Token::Word(Cow::Owned("Elm".into())),
+        span: foreign::Span {
+            source: Cow::Owned("Elm Oak".into()),
+            start: 4,
+        },
+    };
+    assert_eq!(to_borrowed(&example), example);
+    assert_eq!(
+        TokenDef::to_borrowed(&foreign::Token::Number(1)),
+        foreign::Token::Number(1)
+    );
+}
+
+#[allow(dead_code)]
+#[derive(ToBorrowed)]
+#[lifetime(remote = "foreign::Spans")]
+struct SpansDef<'a, const N: usize> {
+    sources: [Cow<'a, str>; N],
+}
+
+#[test]
+fn remote_generic() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Example<'a> {
+        #[lifetime(with = "SpansDef")]
+        spans: foreign::Spans<'a, 2>,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let spans = foreign::Spans {
+        sources: [Cow::Owned("Elm".into()), Cow::Owned("Oak".into())],
+    };
+    assert_eq!(SpansDef::to_borrowed(&spans), spans);
+    let example = Example { spans };
+    assert_eq!(to_borrowed(&example), example);
+}
+
+#[test]
+fn bound_attributes() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Example<'a, T> {
+        #[lifetime(to_borrowed_bound = "for<'t> &'t T: ToBorrowed<Borrowed = T>")]
+        items: Vec<T>,
+        name: Cow<'a, str>,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a, u32>) -> Example<'r, u32> {
+        x.to_borrowed()
+    }
+
+    let example = Example {
+        items: vec![1, 2],
+        name: Cow::Owned("Oak".into()),
+    };
+    assert_eq!(to_borrowed(&example), example);
+}
+
+#[test]
+fn bound_attributes_with_reference_to_type_param() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    struct Example<'a, T> {
+        #[lifetime(bound = "for<'t> &'t T: ToBorrowed<Borrowed = T>")]
+        value: &'a T,
+        name: Cow<'a, str>,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a, u32>) -> Example<'r, u32> {
+        x.to_borrowed()
+    }
+
+    let example = Example {
+        value: &1,
+        name: Cow::Owned("Oak".into()),
+    };
+    assert_eq!(to_borrowed(&example), example);
+}
+
+#[test]
+fn bound_attributes_with_bounded_type() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    #[lifetime(bound = "for<'t> &'t T: ToBorrowed")]
+    struct Example<'a, T: Clone>
+    where
+        T: Debug,
+    {
+        items: Vec<T>,
+        name: Cow<'a, str>,
+    }
+
+    let example = Example {
+        items: vec![Cow::<str>::Owned("Elm".into())],
+        name: Cow::Owned("Oak".into()),
+    };
+    assert_eq!(example.to_borrowed(), example);
+}
+
+#[test]
+fn kept_lifetimes() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    #[lifetime(keep = "'arena")]
+    struct Node<'arena, 'src> {
+        name: Cow<'src, str>,
+        children: &'arena [u32],
+    }
+
+    fn to_borrowed<'r, 'arena>(x: &'r Node<'arena, '_>) -> Node<'arena, 'r> {
+        x.to_borrowed()
+    }
+
+    let arena = vec![1, 2];
+    let node = Node {
+        name: Cow::Owned("Elm".into()),
+        children: &arena,
+    };
+    assert_eq!(to_borrowed(&node), node);
+}
+
+#[test]
+fn kept_lifetime_bounds() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    #[lifetime(keep = "'arena")]
+    struct Node<'arena, 'src: 'arena> {
+        name: Cow<'src, str>,
+        parent: Option<&'arena Node<'arena, 'src>>,
+    }
+
+    fn to_borrowed<'arena, 'src>(x: &'arena Node<'arena, 'src>) -> Node<'arena, 'arena> {
+        x.to_borrowed()
+    }
+
+    let root = Node {
+        name: Cow::Owned("Elm".into()),
+        parent: None,
+    };
+    let node = Node {
+        name: Cow::Owned("Oak".into()),
+        parent: Some(&root),
+    };
+    assert_eq!(to_borrowed(&node), node);
+}
+
+#[test]
+fn type_alias() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    #[lifetime(alias(borrowed = "HeaderRef"))]
+    struct Header<'a, T> {
+        name: Cow<'a, str>,
+        values: Vec<T>,
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Header<'a, Cow<'a, str>>) -> HeaderRef<'r, Cow<'a, str>> {
+        x.to_borrowed()
+    }
+
+    let header = Header {
+        name: Cow::Owned("content".into()),
+        values: vec![Cow::Owned("xml".into())],
+    };
+    assert_eq!(to_borrowed(&header), header);
+}
+
+#[test]
+fn from_attribute() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    #[lifetime(from)]
+    struct Header<'a> {
+        name: Cow<'a, str>,
+    }
+
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    #[lifetime(from)]
+    struct Headers<'a, const N: usize> {
+        name: Cow<'a, str>,
+        values: [Cow<'a, str>; N],
+    }
+
+    fn to_borrowed<'r, 'a>(x: &'r Headers<'a, 1>) -> Headers<'r, 1> {
+        x.into()
+    }
+
+    let header = Header {
+        name: Cow::Owned("content".into()),
+    };
+    assert_eq!(Header::from(&header), header);
+
+    let headers = Headers {
+        name: Cow::Owned("content".into()),
+        values: [Cow::Owned("xml".into())],
+    };
+    assert_eq!(Headers::from(&headers), headers);
+    assert_eq!(to_borrowed(&headers), headers);
+}
+
+mod facade {
+    pub use lifetime;
+}
+
+#[test]
+fn crate_path() {
+    #[derive(ToBorrowed, Debug, PartialEq)]
+    #[lifetime(crate = "facade::lifetime")]
+    struct Example<'a>(Cow<'a, str>);
+
+    fn to_borrowed<'r, 'a>(x: &'r Example<'a>) -> Example<'r> {
+        x.to_borrowed()
+    }
+
+    let example = Example(Cow::Owned("Elm".into()));
+    assert_eq!(to_borrowed(&example), example);
+}
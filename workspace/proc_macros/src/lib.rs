@@ -4,13 +4,93 @@ use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
 /// Derive macro generating an impl of the trait `IntoStatic`.
-#[proc_macro_derive(IntoStatic)]
+///
+/// # Container attributes
+///
+/// - `#[lifetime(crate = "path")]` refers to the `lifetime` crate by `path`,
+///   for when it's re-exported by another crate. Defaults to `::lifetime`.
+/// - `#[lifetime(remote = "path")]` derives for the type at `path` of another crate,
+///   using the derive input as a mirror of its fields.
+///   Instead of an impl, an associated function `into_static` is generated on the mirror,
+///   so a field of the remote type can be converted with `#[lifetime(with = "Mirror")]`.
+/// - `#[lifetime(keep = "'a, 'b")]` keeps the given lifetime parameters unchanged,
+///   instead of converting them. Because the converted type still contains them,
+///   it isn't `'static`, so an associated function `into_static` is generated
+///   instead of an impl. It can't be combined with `from`.
+/// - `#[lifetime(bound = "T: Bound")]` or `#[lifetime(into_static_bound = "T: Bound")]`
+///   replaces the inferred bounds of the impl with the given where predicates.
+/// - `#[lifetime(alias(static = "Name"))]` generates a type alias `Name` for `Example<'static>`,
+///   with the visibility of the type.
+///   The parameters of the alias are the kept lifetimes, the type parameters
+///   and the const parameters of the type. The type parameters are the original types,
+///   like `ExampleOwned<T> = Example<'static, <T as IntoStatic>::Static>`.
+///   An alias `borrowed` is only allowed if the type also derives `ToBorrowed`.
+/// - `#[lifetime(from)]` generates an associated function `from_borrowed` on the static type,
+///   like `Example::<'static>::from_borrowed(example)`, which calls `into_static`.
+///
+/// # Field attributes
+///
+/// - `#[lifetime(with = "module")]` converts the field with `module::into_static`.
+/// - `#[lifetime(into_static_with = "path")]` converts the field with the function `path`.
+/// - `#[lifetime(default)]` or `#[lifetime(skip)]` replaces the field with `Default::default()`.
+/// - `#[lifetime(clone)]` moves the field without converting it.
+/// - `#[lifetime(convert)]` converts the field, even if no generic lifetime is found in its type.
+/// - `#[lifetime(copy)]` moves the field without converting it, even if its type is generic.
+/// - `#[lifetime(bound = "T: Bound")]` or `#[lifetime(into_static_bound = "T: Bound")]`
+///   replaces the bounds inferred for the type parameters
+///   which are only used by fields with a bound.
+#[proc_macro_derive(IntoStatic, attributes(lifetime))]
 pub fn derive_into_static(input: TokenStream) -> TokenStream {
-    lifetime_proc_macros_impl::into_static::derive(parse_macro_input!(input as DeriveInput)).into()
+    lifetime_proc_macros_impl::into_static::derive(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 /// Derive macro generating an impl of the trait `ToBorrowed`.
-#[proc_macro_derive(ToBorrowed)]
+///
+/// Fields without generic lifetimes or type parameters are copied, so they must be `Copy`.
+/// Other fields, like a `String`, can be cloned with `#[lifetime(clone)]`.
+/// References to type parameters, like `&'a T`, are only supported with a bound
+/// which keeps the borrowed type, like `for<'t> &'t T: ToBorrowed<Borrowed = T>`.
+///
+/// # Container attributes
+///
+/// - `#[lifetime(crate = "path")]` refers to the `lifetime` crate by `path`,
+///   for when it's re-exported by another crate. Defaults to `::lifetime`.
+/// - `#[lifetime(remote = "path")]` derives for the type at `path` of another crate,
+///   using the derive input as a mirror of its fields.
+///   Instead of an impl, an associated function `to_borrowed` is generated on the mirror,
+///   so a field of the remote type can be converted with `#[lifetime(with = "Mirror")]`.
+///   It can't be used on types with type parameters.
+/// - `#[lifetime(keep = "'a, 'b")]` keeps the given lifetime parameters unchanged,
+///   instead of converting them.
+/// - `#[lifetime(bound = "T: Bound")]` or `#[lifetime(to_borrowed_bound = "T: Bound")]`
+///   replaces the inferred bounds of the impl with the given where predicates.
+/// - `#[lifetime(alias(borrowed = "Name"))]` generates a type alias `Name` for `Example<'r>`,
+///   with the visibility of the type.
+///   The first parameter of the alias is the lifetime of the borrow,
+///   followed by the kept lifetimes, the type parameters and the const parameters of the type.
+///   The type parameters are the original types,
+///   like `ExampleRef<'r, T> = Example<'r, <&'r T as ToBorrowed>::Borrowed>`.
+///   An alias `static` is only allowed if the type also derives `IntoStatic`.
+/// - `#[lifetime(from)]` generates `impl From<&'r Example<'a>> for Example<'r>`,
+///   which calls `to_borrowed`.
+///   It can't be used on types with type parameters.
+///
+/// # Field attributes
+///
+/// - `#[lifetime(with = "module")]` converts the field with `module::to_borrowed`.
+/// - `#[lifetime(to_borrowed_with = "path")]` converts the field with the function `path`.
+/// - `#[lifetime(default)]` or `#[lifetime(skip)]` replaces the field with `Default::default()`.
+/// - `#[lifetime(clone)]` clones the field instead of converting it.
+/// - `#[lifetime(convert)]` converts the field, even if no generic lifetime is found in its type.
+/// - `#[lifetime(copy)]` copies the field without converting it, even if its type is generic.
+/// - `#[lifetime(bound = "T: Bound")]` or `#[lifetime(to_borrowed_bound = "T: Bound")]`
+///   replaces the bounds inferred for the type parameters
+///   which are only used by fields with a bound.
+#[proc_macro_derive(ToBorrowed, attributes(lifetime))]
 pub fn derive_to_borrowed(input: TokenStream) -> TokenStream {
-    lifetime_proc_macros_impl::to_borrowed::derive(parse_macro_input!(input as DeriveInput)).into()
+    lifetime_proc_macros_impl::to_borrowed::derive(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
@@ -0,0 +1,45 @@
+use crate::ident::respan;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{Generics, Ident, Path};
+
+/// Implements the marker trait `marker` of `__private` for the derive input `ident`,
+/// showing that this derive generates the alias of the type.
+pub(crate) fn alias_marker_impl(
+    crate_path: &Path,
+    marker: &str,
+    ident: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let marker = Ident::new(marker, ident.span());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #crate_path::__private::#marker for #ident #ty_generics #where_clause {}
+    }
+}
+
+/// Asserts that the derive input `ident` implements the marker trait
+/// checked by `assert_fn` of `__private`, because the other derive generates the `alias`.
+/// The error is spanned at the name of the alias.
+pub(crate) fn assert_alias_derived(
+    crate_path: &Path,
+    assert_fn: &str,
+    alias: &Ident,
+    ident: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let assert_fn = Ident::new(assert_fn, alias.span());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = respan(quote!(#ident #ty_generics), alias.span());
+    let assertion = quote_spanned! {alias.span()=>
+        #crate_path::__private::#assert_fn::<#ty>();
+    };
+    quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            fn assert_alias_derived #impl_generics () #where_clause {
+                #assertion
+            }
+        };
+    }
+}
@@ -0,0 +1,280 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_quote, punctuated::Punctuated, token::Comma, Attribute, DeriveInput, Error, Field, Ident,
+    Lifetime, Lit, LitStr, Meta, MetaNameValue, NestedMeta, Path, WherePredicate,
+};
+
+/// Options of the derive input, parsed from its `#[lifetime(...)]` attributes.
+pub(crate) struct ContainerAttrs {
+    /// Path to the `lifetime` crate, used in the generated code.
+    pub crate_path: Path,
+    /// The foreign type mirrored by the derive input, which gets conversion functions
+    /// instead of trait impls.
+    pub remote: Option<Path>,
+    /// The lifetime parameters which are left unchanged by the conversion.
+    pub keep: Vec<Lifetime>,
+    pub bounds: BoundAttrs,
+    /// Name of the type alias generated for the static type.
+    pub static_alias: Option<Ident>,
+    /// Name of the type alias generated for the borrowed type.
+    pub borrowed_alias: Option<Ident>,
+    /// Whether `From` conversions are generated next to the impls.
+    pub from: bool,
+}
+
+impl ContainerAttrs {
+    pub(crate) fn from_input(input: &DeriveInput) -> syn::Result<Self> {
+        let mut crate_path = None;
+        let mut remote = None;
+        let mut keep = Vec::new();
+        let mut bounds = BoundAttrs::default();
+        let mut static_alias = None;
+        let mut borrowed_alias = None;
+        let mut from = None;
+        for meta in lifetime_metas(&input.attrs)? {
+            match &meta {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    if bounds.parse(name_value)? {
+                        continue;
+                    }
+                    let path = &name_value.path;
+                    if path.is_ident("keep") {
+                        keep.extend(kept_lifetimes(input, lit_str(&name_value.lit)?)?);
+                        continue;
+                    }
+                    let slot = if path.is_ident("crate") {
+                        &mut crate_path
+                    } else if path.is_ident("remote") {
+                        &mut remote
+                    } else {
+                        return Err(unknown_attribute(&meta));
+                    };
+                    set_once(slot, lit_str(&name_value.lit)?.parse()?, path)?;
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("alias") => {
+                    for meta in &list.nested {
+                        let name_value = match meta {
+                            NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+                            _ => return Err(unknown_attribute(meta)),
+                        };
+                        let path = &name_value.path;
+                        let slot = if path.is_ident("static") {
+                            &mut static_alias
+                        } else if path.is_ident("borrowed") {
+                            &mut borrowed_alias
+                        } else {
+                            return Err(unknown_attribute(meta));
+                        };
+                        set_once(slot, lit_str(&name_value.lit)?.parse()?, path)?;
+                    }
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("from") => {
+                    set_once(&mut from, path.clone(), path)?
+                }
+                _ => return Err(unknown_attribute(&meta)),
+            }
+        }
+        if let (Some(from), Some(_)) = (&from, &remote) {
+            return Err(Error::new_spanned(
+                from,
+                "the lifetime attribute `from` can't be used with `remote`, \
+                 because the conversions would be implemented for a foreign type",
+            ));
+        }
+        Ok(Self {
+            crate_path: crate_path.unwrap_or_else(|| parse_quote!(::lifetime)),
+            remote,
+            keep,
+            bounds,
+            static_alias,
+            borrowed_alias,
+            from: from.is_some(),
+        })
+    }
+}
+
+/// Parses the lifetimes of a `keep` attribute, which must be lifetime parameters of `input`.
+fn kept_lifetimes(input: &DeriveInput, lit: &LitStr) -> syn::Result<Vec<Lifetime>> {
+    let lifetimes = lit.parse_with(Punctuated::<Lifetime, Comma>::parse_terminated)?;
+    for lifetime in &lifetimes {
+        if !input
+            .generics
+            .lifetimes()
+            .any(|def| def.lifetime == *lifetime)
+        {
+            return Err(Error::new_spanned(
+                lifetime,
+                "only lifetime parameters of the type can be kept",
+            ));
+        }
+    }
+    Ok(lifetimes.into_iter().collect())
+}
+
+/// Options of a field, parsed from its `#[lifetime(...)]` attributes.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    with: Option<Path>,
+    into_static_with: Option<Path>,
+    to_borrowed_with: Option<Path>,
+    /// Replaces the field with `Default::default()`, set by `default` or its alias `skip`.
+    pub default: bool,
+    skip: bool,
+    pub clone: bool,
+    /// Converts the field, even if its type has no generic lifetime or type parameter.
+    pub convert: bool,
+    /// Copies or moves the field, even if its type has a generic lifetime or type parameter.
+    pub copy: bool,
+    pub bounds: BoundAttrs,
+}
+
+impl FieldAttrs {
+    pub(crate) fn from_field(field: &Field) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        for meta in lifetime_metas(&field.attrs)? {
+            match &meta {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    if attrs.bounds.parse(name_value)? {
+                        continue;
+                    }
+                    let path = &name_value.path;
+                    let slot = if path.is_ident("with") {
+                        &mut attrs.with
+                    } else if path.is_ident("into_static_with") {
+                        &mut attrs.into_static_with
+                    } else if path.is_ident("to_borrowed_with") {
+                        &mut attrs.to_borrowed_with
+                    } else {
+                        return Err(unknown_attribute(&meta));
+                    };
+                    set_once(slot, lit_str(&name_value.lit)?.parse()?, path)?;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("default") => {
+                    attrs.default = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => attrs.skip = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("clone") => attrs.clone = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("convert") => {
+                    attrs.convert = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("copy") => attrs.copy = true,
+                _ => return Err(unknown_attribute(&meta)),
+            }
+        }
+        let with_count = attrs.with.is_some() as usize
+            + (attrs.into_static_with.is_some() || attrs.to_borrowed_with.is_some()) as usize;
+        let flag_count = [
+            attrs.default,
+            attrs.skip,
+            attrs.clone,
+            attrs.convert,
+            attrs.copy,
+        ]
+        .iter()
+        .filter(|flag| **flag)
+        .count();
+        if with_count + flag_count > 1 {
+            return Err(Error::new_spanned(
+                field,
+                "only one of the lifetime attributes `with`, `into_static_with`/`to_borrowed_with`, \
+                 `default`/`skip`, `clone`, `convert` and `copy` can be used on a field",
+            ));
+        }
+        attrs.default |= attrs.skip;
+        Ok(attrs)
+    }
+
+    /// The function converting the field for `IntoStatic`, if there is a custom one.
+    pub(crate) fn custom_into_static(&self) -> Option<TokenStream> {
+        match (&self.with, &self.into_static_with) {
+            (Some(with), _) => Some(quote!(#with::into_static)),
+            (None, Some(into_static_with)) => Some(quote!(#into_static_with)),
+            (None, None) => None,
+        }
+    }
+
+    /// The function converting the field for `ToBorrowed`, if there is a custom one.
+    pub(crate) fn custom_to_borrowed(&self) -> Option<TokenStream> {
+        match (&self.with, &self.to_borrowed_with) {
+            (Some(with), _) => Some(quote!(#with::to_borrowed)),
+            (None, Some(to_borrowed_with)) => Some(quote!(#to_borrowed_with)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// The where predicates written by the user to replace the inferred bounds of the impls,
+/// parsed from the `bound`, `into_static_bound` and `to_borrowed_bound` attributes.
+#[derive(Default)]
+pub(crate) struct BoundAttrs {
+    bound: Option<Vec<WherePredicate>>,
+    into_static_bound: Option<Vec<WherePredicate>>,
+    to_borrowed_bound: Option<Vec<WherePredicate>>,
+}
+
+impl BoundAttrs {
+    /// Parses `name_value` if it's one of the bound attributes, returning whether it is.
+    fn parse(&mut self, name_value: &MetaNameValue) -> syn::Result<bool> {
+        let path = &name_value.path;
+        let slot = if path.is_ident("bound") {
+            &mut self.bound
+        } else if path.is_ident("into_static_bound") {
+            &mut self.into_static_bound
+        } else if path.is_ident("to_borrowed_bound") {
+            &mut self.to_borrowed_bound
+        } else {
+            return Ok(false);
+        };
+        let predicates = lit_str(&name_value.lit)?
+            .parse_with(Punctuated::<WherePredicate, Comma>::parse_terminated)?;
+        set_once(slot, predicates.into_iter().collect(), path)?;
+        Ok(true)
+    }
+
+    /// The bounds for the `IntoStatic` impl, if the user wrote any.
+    pub(crate) fn custom_into_static(&self) -> Option<&[WherePredicate]> {
+        self.into_static_bound.as_deref().or(self.bound.as_deref())
+    }
+
+    /// The bounds for the `ToBorrowed` impl, if the user wrote any.
+    pub(crate) fn custom_to_borrowed(&self) -> Option<&[WherePredicate]> {
+        self.to_borrowed_bound.as_deref().or(self.bound.as_deref())
+    }
+}
+
+/// The nested metas of all `#[lifetime(...)]` attributes.
+fn lifetime_metas(attrs: &[Attribute]) -> syn::Result<Vec<NestedMeta>> {
+    let mut metas = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("lifetime")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => metas.extend(list.nested),
+            other => {
+                return Err(Error::new_spanned(
+                    other,
+                    "expected a list of attributes, like `#[lifetime(default)]`",
+                ))
+            }
+        }
+    }
+    Ok(metas)
+}
+
+fn lit_str(lit: &Lit) -> syn::Result<&LitStr> {
+    match lit {
+        Lit::Str(lit_str) => Ok(lit_str),
+        other => Err(Error::new_spanned(other, "expected a string literal")),
+    }
+}
+
+fn set_once<T>(slot: &mut Option<T>, value: T, path: &Path) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(Error::new_spanned(path, "duplicate lifetime attribute"));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+fn unknown_attribute(meta: &NestedMeta) -> Error {
+    Error::new_spanned(meta, "unknown lifetime attribute")
+}
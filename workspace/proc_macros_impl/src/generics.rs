@@ -1,39 +1,278 @@
-use syn::{GenericParam, Generics, Lifetime, LifetimeDef};
-
-pub(crate) fn assert_generics_are_supported(generics: &Generics) {
-    for param in &generics.params {
-        match param {
-            GenericParam::Type(type_param) => {
-                panic!(
-                    "Generic type parameters are not supported. The type parameter is {}",
-                    type_param.ident
-                )
+use crate::{attr::FieldAttrs, type_::type_has_type_param};
+use std::slice;
+use syn::{
+    parse_quote,
+    visit_mut::{self, VisitMut},
+    Data, Field, Fields, GenericParam, Generics, Ident, Lifetime, LifetimeDef, PredicateLifetime,
+    Type, TypePath, WhereClause, WherePredicate,
+};
+
+/// Adds `lifetime` as the first parameter of `generics`.
+///
+/// The bounds of the other parameters are kept.
+/// No explicit `'a: 'ref_` bounds are added, they are implied by `&'ref_ Example<'a>`.
+/// Explicit bounds would break `for<'t> &'t Example<'a>: ToBorrowed` for recursive types.
+pub(crate) fn add_lifetime(mut generics: Generics, lifetime: Lifetime) -> Generics {
+    generics
+        .params
+        .insert(0, GenericParam::Lifetime(LifetimeDef::new(lifetime)));
+    generics
+}
+
+/// The generics of a type alias for the converted type,
+/// which are the parameters of `generics` which aren't replaced, without their bounds.
+pub(crate) fn alias_generics(generics: &Generics, kept_lifetimes: &[Lifetime]) -> Generics {
+    let params = generics.params.iter().filter_map(|param| match param {
+        GenericParam::Lifetime(def) if kept_lifetimes.contains(&def.lifetime) => Some(
+            GenericParam::Lifetime(LifetimeDef::new(def.lifetime.clone())),
+        ),
+        GenericParam::Lifetime(_) => None,
+        GenericParam::Type(type_param) => Some(GenericParam::Type(type_param.ident.clone().into())),
+        GenericParam::Const(const_param) => {
+            let ident = &const_param.ident;
+            let ty = &const_param.ty;
+            Some(parse_quote!(const #ident: #ty))
+        }
+    });
+    parse_quote!(<#(#params),*>)
+}
+
+/// Replaces the generic parameters of the derive input
+/// with the ones of the converted type.
+pub(crate) struct ReplaceGenerics<'a> {
+    lifetimes: Vec<Ident>,
+    type_params: Vec<Ident>,
+    new_lifetime: &'a Lifetime,
+    new_type: &'a dyn Fn(&Ident) -> Type,
+}
+
+impl<'a> ReplaceGenerics<'a> {
+    /// Every lifetime parameter of `generics`, except the `kept_lifetimes`,
+    /// is replaced by `new_lifetime` and every type parameter `T` by `new_type(T)`.
+    pub(crate) fn new(
+        generics: &Generics,
+        kept_lifetimes: &[Lifetime],
+        new_lifetime: &'a Lifetime,
+        new_type: &'a dyn Fn(&Ident) -> Type,
+    ) -> Self {
+        Self {
+            lifetimes: generics
+                .lifetimes()
+                .filter(|def| !kept_lifetimes.contains(&def.lifetime))
+                .map(|def| def.lifetime.ident.clone())
+                .collect(),
+            type_params: type_params(generics),
+            new_lifetime,
+            new_type,
+        }
+    }
+
+    pub(crate) fn ty(&mut self, ty: &Type) -> Type {
+        let mut ty = ty.clone();
+        self.visit_type_mut(&mut ty);
+        ty
+    }
+
+    /// The generics of an impl for the converted type, which are the parameters of `generics`
+    /// except the replaced lifetimes, with their bounds converted.
+    pub(crate) fn converted_generics(&mut self, generics: &Generics) -> Generics {
+        let mut converted = generics.clone();
+        converted.params = generics
+            .params
+            .iter()
+            .filter(|param| match param {
+                GenericParam::Lifetime(def) => !self.lifetimes.contains(&def.lifetime.ident),
+                _ => true,
+            })
+            .cloned()
+            .collect();
+        self.visit_generics_mut(&mut converted);
+        converted
+    }
+
+    /// The where clause of the generated impl.
+    ///
+    /// Contains the where clause of the input, `param_bound` for every type parameter
+    /// with inferred bounds, the bounds of the input restated for the converted type,
+    /// which are required for the converted type to be well-formed,
+    /// and the bounds written by the user.
+    /// The bounds written by the user only replace `param_bound`,
+    /// the restated bounds are always required.
+    pub(crate) fn where_clause(
+        &mut self,
+        generics: &Generics,
+        bounds: &Bounds,
+        param_bound: &dyn Fn(&Ident) -> WherePredicate,
+    ) -> Option<WhereClause> {
+        let mut predicates: Vec<WherePredicate> = generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| where_clause.predicates.iter().cloned())
+            .collect();
+        for type_param in generics.type_params() {
+            if bounds.inferred_params.contains(&type_param.ident) {
+                predicates.push(param_bound(&type_param.ident));
+            }
+            if !type_param.bounds.is_empty() {
+                let ident = &type_param.ident;
+                let bounds = &type_param.bounds;
+                let mut predicate = parse_quote!(#ident: #bounds);
+                self.visit_where_predicate_mut(&mut predicate);
+                predicates.push(predicate);
             }
-            GenericParam::Lifetime(lifetime_param) => {
-                if !lifetime_param.bounds.is_empty() {
-                    panic!("Generic lifetime parameters with bounds are not supported. The lifetime with bounds is '{}", lifetime_param.lifetime.ident)
+        }
+        for lifetime_def in generics.lifetimes().filter(|def| !def.bounds.is_empty()) {
+            predicates.extend(self.converted_lifetime_predicate(PredicateLifetime {
+                lifetime: lifetime_def.lifetime.clone(),
+                colon_token: Default::default(),
+                bounds: lifetime_def.bounds.clone(),
+            }));
+        }
+        for predicate in generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| &where_clause.predicates)
+        {
+            match predicate {
+                WherePredicate::Type(_) => {
+                    let mut predicate = predicate.clone();
+                    self.visit_where_predicate_mut(&mut predicate);
+                    predicates.push(predicate);
+                }
+                WherePredicate::Lifetime(predicate_lifetime) => {
+                    predicates.extend(self.converted_lifetime_predicate(predicate_lifetime.clone()))
                 }
+                WherePredicate::Eq(_) => {}
             }
-            GenericParam::Const(const_param) => {
-                panic!(
-                    "Const generic parameters are not supported. The const parameter is {}",
-                    const_param.ident
-                )
+        }
+        predicates.extend(bounds.custom.iter().cloned());
+        if predicates.is_empty() {
+            None
+        } else {
+            Some(parse_quote!(where #(#predicates),*))
+        }
+    }
+}
+
+impl ReplaceGenerics<'_> {
+    /// Restates a bound between lifetimes for the converted lifetimes,
+    /// like `'ref_: 'arena` for `'src: 'arena` if `'arena` is kept.
+    /// Bounds which always hold, like `'ref_: 'ref_` or `'static: 'arena`, are left out.
+    fn converted_lifetime_predicate(
+        &mut self,
+        mut predicate: PredicateLifetime,
+    ) -> Option<WherePredicate> {
+        self.visit_predicate_lifetime_mut(&mut predicate);
+        if predicate.lifetime.ident == "static" {
+            return None;
+        }
+        let lifetime = &predicate.lifetime;
+        let bounds: Vec<&Lifetime> = predicate
+            .bounds
+            .iter()
+            .filter(|bound| *bound != lifetime)
+            .collect();
+        if bounds.is_empty() {
+            None
+        } else {
+            Some(parse_quote!(#lifetime: #(#bounds)+*))
+        }
+    }
+}
+
+impl VisitMut for ReplaceGenerics<'_> {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if self.lifetimes.contains(&lifetime.ident) {
+            *lifetime = self.new_lifetime.clone();
+        }
+    }
+
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::Path(TypePath { qself: None, path }) = ty {
+            if let Some(ident) = path.get_ident() {
+                if self.type_params.contains(ident) {
+                    *ty = (self.new_type)(ident);
+                    return;
+                }
             }
         }
+        visit_mut::visit_type_mut(self, ty)
     }
 }
 
-pub(crate) fn add_lifetime(mut generics: Generics, lifetime: Lifetime) -> Generics {
-    generics
-        .params
-        .insert(0, GenericParam::Lifetime(LifetimeDef::new(lifetime)));
-    generics
+/// The bounds of the generated impl, either inferred from the type parameters
+/// or written by the user with the bound attributes.
+pub(crate) struct Bounds {
+    /// The type parameters which get inferred bounds.
+    inferred_params: Vec<Ident>,
+    custom: Vec<WherePredicate>,
 }
 
-pub(crate) fn replace_lifetimes(mut generics: Generics, new: &Lifetime) -> Generics {
-    for old in generics.lifetimes_mut() {
-        *old = LifetimeDef::new(new.clone());
+impl Bounds {
+    /// A bound on the container replaces all inferred bounds.
+    /// A bound on a field replaces the inferred bounds of the type parameters
+    /// which are only used by fields with a bound.
+    pub(crate) fn new(
+        generics: &Generics,
+        data: &Data,
+        container_bound: Option<&[WherePredicate]>,
+        field_bound: &dyn Fn(&FieldAttrs) -> Option<Vec<WherePredicate>>,
+    ) -> syn::Result<Self> {
+        if let Some(container_bound) = container_bound {
+            return Ok(Self {
+                inferred_params: Vec::new(),
+                custom: container_bound.to_vec(),
+            });
+        }
+        let mut custom = Vec::new();
+        let mut inferred_fields = Vec::new();
+        let mut custom_fields = Vec::new();
+        for field in data_fields(data) {
+            match field_bound(&FieldAttrs::from_field(field)?) {
+                Some(bound) => {
+                    custom.extend(bound);
+                    custom_fields.push(field);
+                }
+                None => inferred_fields.push(field),
+            }
+        }
+        let used_by = |fields: &[&Field], param: &Ident| {
+            fields
+                .iter()
+                .any(|field| type_has_type_param(&field.ty, slice::from_ref(param)))
+        };
+        Ok(Self {
+            inferred_params: type_params(generics)
+                .into_iter()
+                .filter(|param| used_by(&inferred_fields, param) || !used_by(&custom_fields, param))
+                .collect(),
+            custom,
+        })
     }
+
+    /// The type parameters which get inferred bounds.
+    pub(crate) fn inferred_params(&self) -> &[Ident] {
+        &self.inferred_params
+    }
+}
+
+/// All fields of all variants of `data`.
+fn data_fields(data: &Data) -> impl Iterator<Item = &Field> {
+    let fields: Vec<&Fields> = match data {
+        Data::Struct(struct_data) => vec![&struct_data.fields],
+        Data::Enum(enum_data) => enum_data
+            .variants
+            .iter()
+            .map(|variant| &variant.fields)
+            .collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    fields.into_iter().flatten()
+}
+
+pub(crate) fn type_params(generics: &Generics) -> Vec<Ident> {
     generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect()
 }
@@ -1,22 +1,42 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
+use syn::Path;
 
 pub(crate) fn tuple_field_ident(index: usize) -> Ident {
     Ident::new(&format!("x{}", index), Span::mixed_site())
 }
 
+/// The parameter of the functions generated for a remote type,
+/// which takes the place of `self`.
+pub(crate) fn remote_receiver() -> TokenStream {
+    let ident = Ident::new("value", Span::mixed_site());
+    quote!(#ident)
+}
+
+/// Moves all `tokens` to the location of `span`, so errors about them point at it.
+/// The hygiene of the tokens is kept.
+pub(crate) fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            token.set_span(token.span().located_at(span));
+            token
+        })
+        .collect()
+}
+
 pub(crate) struct EnumVariantIdent {
-    pub enum_ident: Ident,
+    pub enum_path: Path,
     pub variant_ident: Ident,
 }
 
 impl quote::ToTokens for EnumVariantIdent {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self {
-            enum_ident,
+            enum_path,
             variant_ident,
         } = self;
-        let ident = quote! { #enum_ident :: #variant_ident };
+        let ident = quote! { #enum_path :: #variant_ident };
         tokens.extend(std::iter::once(ident));
     }
 }
@@ -1,97 +1,197 @@
 use crate::{
-    generics::{assert_generics_are_supported, replace_lifetimes},
-    ident::tuple_field_ident,
+    alias::{alias_marker_impl, assert_alias_derived},
+    attr::{ContainerAttrs, FieldAttrs},
+    generics::{alias_generics, type_params, Bounds, ReplaceGenerics},
+    ident::{remote_receiver, tuple_field_ident},
     modified_clone::ModifiedClone,
-    type_::type_has_generic_lifetime,
+    type_::{assert_type_is_supported, field_needs_conversion, ConvertedParams},
 };
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use std::convert::TryFrom;
-use syn::{DeriveInput, Field, Index, Lifetime};
+use syn::{parse_quote, DeriveInput, Error, Field, Ident, Index, Lifetime, Path, Type};
 
-pub fn derive(input: DeriveInput) -> TokenStream {
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
     let static_lifetime = Lifetime::new("'static", Span::mixed_site());
+    let attrs = ContainerAttrs::from_input(&input)?;
+    let crate_path = &attrs.crate_path;
     let generics = input.generics;
-    assert_generics_are_supported(&generics);
-    let static_generics = replace_lifetimes(generics.clone(), &static_lifetime);
     let ident = input.ident;
+    let (impl_generics, ty_generics, input_where_clause) = generics.split_for_impl();
+    let (path, receiver) = match &attrs.remote {
+        Some(remote) => (remote.clone(), remote_receiver()),
+        None => (Path::from(ident.clone()), quote!(self)),
+    };
+    let self_ty: Type = parse_quote!(#path #ty_generics);
+    let static_param = |param: &Ident| parse_quote!(<#param as #crate_path::IntoStatic>::Static);
+    let mut replace_generics =
+        ReplaceGenerics::new(&generics, &attrs.keep, &static_lifetime, &static_param);
+    let static_ty = replace_generics.ty(&self_ty);
+    let bounds = Bounds::new(
+        &generics,
+        &input.data,
+        attrs.bounds.custom_into_static(),
+        &|field_attrs| field_attrs.bounds.custom_into_static().map(<[_]>::to_vec),
+    )?;
+    let where_clause = replace_generics.where_clause(
+        &generics,
+        &bounds,
+        &|param| parse_quote!(#param: #crate_path::IntoStatic),
+    );
+    let params = ConvertedParams {
+        type_params: type_params(&generics),
+        kept_lifetimes: attrs.keep.clone(),
+    };
     let fn_body = ModifiedClone {
-        ident: &ident,
+        path: &path,
+        receiver: &receiver,
         data: &input.data,
-        struct_field_init: &struct_field_initialization,
-        enum_field_init: &enum_field_initialization,
+        struct_field_init: &|index, field| {
+            struct_field_initialization(index, field, &receiver, &params)
+        },
+        enum_field_init: &|index, field| enum_field_initialization(index, field, &params),
     }
-    .expression();
-    quote! {
-        impl #generics lifetime::IntoStatic for #ident #generics {
-            type Static = #ident #static_generics;
-
-            fn into_static(self) -> #ident #static_generics {
-                use lifetime::IntoStatic;
+    .expression()?;
+    let vis = &input.vis;
+    // A type with kept lifetimes isn't `'static` after the conversion,
+    // so it gets an associated function instead of an `IntoStatic` impl.
+    let conversion = if attrs.remote.is_some() || !attrs.keep.is_empty() {
+        let self_param = match &attrs.remote {
+            Some(_) => quote!(#receiver: #self_ty),
+            None => quote!(self),
+        };
+        quote! {
+            impl #impl_generics #ident #ty_generics #input_where_clause {
+                #vis fn into_static(#self_param) -> #static_ty #where_clause {
+                    use #crate_path::IntoStatic;
 
-                #fn_body
+                    #fn_body
+                }
             }
         }
-    }
-}
+    } else {
+        quote! {
+            impl #impl_generics #crate_path::IntoStatic for #self_ty #where_clause {
+                type Static = #static_ty;
 
-fn struct_field_initialization(index: usize, field: &Field) -> TokenStream {
-    match &field.ident {
-        Some(ident) => {
-            if type_has_generic_lifetime(&field.ty) {
-                quote! {
-                    #ident: self.#ident.into_static(),
-                }
-            } else {
-                quote! {
-                    #ident: self.#ident,
+                fn into_static(self) -> #static_ty {
+                    use #crate_path::IntoStatic;
+
+                    #fn_body
                 }
             }
         }
+    };
+    let alias = attrs.static_alias.as_ref().map(|alias| {
+        let alias_generics = alias_generics(&generics, &attrs.keep);
+        let (alias_params, _, _) = alias_generics.split_for_impl();
+        let marker_impl = alias_marker_impl(crate_path, "StaticAlias", &ident, &generics);
+        quote! {
+            #vis type #alias #alias_params = #static_ty;
+            #marker_impl
+        }
+    });
+    let borrowed_alias_assertion = attrs.borrowed_alias.as_ref().map(|alias| {
+        assert_alias_derived(
+            crate_path,
+            "assert_borrowed_alias",
+            alias,
+            &ident,
+            &generics,
+        )
+    });
+    let from = if attrs.from {
+        if let Some(lifetime) = attrs.keep.first() {
+            return Err(Error::new_spanned(
+                lifetime,
+                "the lifetime attribute `from` can't be used with `keep`, \
+                 because a type with kept lifetimes doesn't implement `IntoStatic`",
+            ));
+        }
+        let unchanged_param = |param: &Ident| parse_quote!(#param);
+        let mut replace_lifetimes =
+            ReplaceGenerics::new(&generics, &attrs.keep, &static_lifetime, &unchanged_param);
+        let static_generics = replace_lifetimes.converted_generics(&generics);
+        let (static_impl_generics, _, static_where_clause) = static_generics.split_for_impl();
+        let static_self_ty = replace_lifetimes.ty(&self_ty);
+        Some(quote! {
+            impl #static_impl_generics #static_self_ty #static_where_clause {
+                /// Converts a value into this static type, with `IntoStatic::into_static`.
+                #vis fn from_borrowed(value: impl #crate_path::IntoStatic<Static = Self>) -> Self {
+                    #crate_path::IntoStatic::into_static(value)
+                }
+            }
+        })
+    } else {
+        None
+    };
+    Ok(quote! {
+        #conversion
+        #from
+        #alias
+        #borrowed_alias_assertion
+    })
+}
+
+fn struct_field_initialization(
+    index: usize,
+    field: &Field,
+    receiver: &TokenStream,
+    params: &ConvertedParams,
+) -> syn::Result<TokenStream> {
+    let value = match &field.ident {
+        Some(ident) => quote!(#receiver.#ident),
         None => {
             let index = Index {
                 index: u32::try_from(index).unwrap(),
                 span: Span::mixed_site(),
             };
-            if type_has_generic_lifetime(&field.ty) {
-                quote! {
-                    self.#index.into_static(),
-                }
-            } else {
-                quote! {
-                    self.#index,
-                }
-            }
+            quote!(#receiver.#index)
         }
-    }
+    };
+    field_initialization(field, value, params)
 }
 
-fn enum_field_initialization(index: usize, field: &Field) -> TokenStream {
-    match &field.ident {
-        Some(ident) => {
-            if type_has_generic_lifetime(&field.ty) {
-                quote! {
-                    #ident: #ident.into_static(),
-                }
-            } else {
-                quote! {
-                    #ident: #ident,
-                }
-            }
-        }
+fn enum_field_initialization(
+    index: usize,
+    field: &Field,
+    params: &ConvertedParams,
+) -> syn::Result<TokenStream> {
+    let value = match &field.ident {
+        Some(ident) => quote!(#ident),
         None => {
             let tuple_field_ident = tuple_field_ident(index);
-            if type_has_generic_lifetime(&field.ty) {
-                quote! {
-                    #tuple_field_ident.into_static(),
-                }
-            } else {
-                quote! {
-                    #tuple_field_ident,
-                }
-            }
+            quote!(#tuple_field_ident)
         }
-    }
+    };
+    field_initialization(field, value, params)
+}
+
+/// Initializes a field of the static value from the field `value` of `self`.
+fn field_initialization(
+    field: &Field,
+    value: TokenStream,
+    params: &ConvertedParams,
+) -> syn::Result<TokenStream> {
+    let attrs = FieldAttrs::from_field(field)?;
+    let static_value = if let Some(into_static_with) = attrs.custom_into_static() {
+        quote!(#into_static_with(#value))
+    } else if attrs.default {
+        quote!({
+            let _ = #value;
+            ::core::default::Default::default()
+        })
+    } else if attrs.clone || attrs.copy || !(attrs.convert || field_needs_conversion(field, params))
+    {
+        value
+    } else {
+        assert_type_is_supported(&field.ty, &params.kept_lifetimes)?;
+        quote!(#value.into_static())
+    };
+    Ok(match &field.ident {
+        Some(ident) => quote!(#ident: #static_value,),
+        None => quote!(#static_value,),
+    })
 }
 
 #[cfg(test)]
@@ -99,9 +199,14 @@ mod tests {
     use super::*;
 
     fn test_derive_input_to_output(input: TokenStream, expected: TokenStream) {
-        let actual = derive(parse(input));
+        let actual = derive(parse(input)).unwrap();
         println!("{:#}", actual);
-        assert_eq!(parse::<syn::Item>(actual), parse::<syn::Item>(expected),);
+        assert_eq!(parse::<syn::File>(actual), parse::<syn::File>(expected),);
+    }
+
+    fn test_derive_input_to_error(input: TokenStream, expected: &str) {
+        let actual = derive(parse(input)).unwrap_err();
+        assert_eq!(actual.to_string(), expected);
     }
 
     #[track_caller]
@@ -118,11 +223,11 @@ mod tests {
             }
         };
         let expected = quote! {
-            impl<'a> lifetime::IntoStatic for Example<'a> {
+            impl<'a> ::lifetime::IntoStatic for Example<'a> {
                 type Static = Example<'static>;
 
                 fn into_static(self) -> Example<'static> {
-                    use lifetime::IntoStatic;
+                    use ::lifetime::IntoStatic;
 
                     Example {
                         primitive: self.primitive,
@@ -140,11 +245,11 @@ mod tests {
             struct Example<'a>(usize, Cow<'a, str>);
         };
         let expected = quote! {
-            impl<'a> lifetime::IntoStatic for Example<'a> {
+            impl<'a> ::lifetime::IntoStatic for Example<'a> {
                 type Static = Example<'static>;
 
                 fn into_static(self) -> Example<'static> {
-                    use lifetime::IntoStatic;
+                    use ::lifetime::IntoStatic;
 
                     Example(self.0, self.1.into_static(),)
                 }
@@ -164,11 +269,11 @@ mod tests {
             }
         };
         let expected = quote! {
-            impl<'a> lifetime::IntoStatic for Example<'a> {
+            impl<'a> ::lifetime::IntoStatic for Example<'a> {
                 type Static = Example<'static>;
 
                 fn into_static(self) -> Example<'static> {
-                    use lifetime::IntoStatic;
+                    use ::lifetime::IntoStatic;
 
                     match self {
                         Example::Primitive0 { number, } => Example::Primitive0 { number: number, },
@@ -182,17 +287,46 @@ mod tests {
         test_derive_input_to_output(input, expected);
     }
 
+    #[test]
+    fn derive_enum_with_empty_variants() {
+        let input = quote! {
+            enum Example<'a> {
+                Unit,
+                EmptyNamed {},
+                EmptyTuple(),
+                Cow(Cow<'a, str>),
+            }
+        };
+        let expected = quote! {
+            impl<'a> ::lifetime::IntoStatic for Example<'a> {
+                type Static = Example<'static>;
+
+                fn into_static(self) -> Example<'static> {
+                    use ::lifetime::IntoStatic;
+
+                    match self {
+                        Example::Unit => Example::Unit,
+                        Example::EmptyNamed {} => Example::EmptyNamed {},
+                        Example::EmptyTuple() => Example::EmptyTuple(),
+                        Example::Cow(x0,) => Example::Cow(x0.into_static(),),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
     #[test]
     fn derive_struct_with_static_reference() {
         let input = quote! {
             struct Example<'a>(&'static Location<'static>, Cow<'a, str>);
         };
         let expected = quote! {
-            impl<'a> lifetime::IntoStatic for Example<'a> {
+            impl<'a> ::lifetime::IntoStatic for Example<'a> {
                 type Static = Example<'static>;
 
                 fn into_static(self) -> Example<'static> {
-                    use lifetime::IntoStatic;
+                    use ::lifetime::IntoStatic;
 
                     Example(self.0, self.1.into_static(),)
                 }
@@ -202,29 +336,473 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
     fn derive_struct_with_generic_type() {
         let input = quote! {
-            struct Example<T>(T);
+            struct Example<'a, T: Clone>(Vec<T>, Cow<'a, str>, usize)
+            where
+                T: Debug;
+        };
+        let expected = quote! {
+            impl<'a, T: Clone> ::lifetime::IntoStatic for Example<'a, T>
+            where
+                T: Debug,
+                T: ::lifetime::IntoStatic,
+                <T as ::lifetime::IntoStatic>::Static: Clone,
+                <T as ::lifetime::IntoStatic>::Static: Debug
+            {
+                type Static = Example<'static, <T as ::lifetime::IntoStatic>::Static>;
+
+                fn into_static(self) -> Example<'static, <T as ::lifetime::IntoStatic>::Static> {
+                    use ::lifetime::IntoStatic;
+
+                    Example(self.0.into_static(), self.1.into_static(), self.2,)
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_container_bound() {
+        let input = quote! {
+            #[lifetime(into_static_bound = "T: IntoStatic, T::Static: Send")]
+            struct Example<'a, T: Clone>(Vec<T>, Cow<'a, str>)
+            where
+                T: Debug;
+        };
+        let expected = quote! {
+            impl<'a, T: Clone> ::lifetime::IntoStatic for Example<'a, T>
+            where
+                T: Debug,
+                <T as ::lifetime::IntoStatic>::Static: Clone,
+                <T as ::lifetime::IntoStatic>::Static: Debug,
+                T: IntoStatic,
+                T::Static: Send
+            {
+                type Static = Example<'static, <T as ::lifetime::IntoStatic>::Static>;
+
+                fn into_static(self) -> Example<'static, <T as ::lifetime::IntoStatic>::Static> {
+                    use ::lifetime::IntoStatic;
+
+                    Example(self.0.into_static(), self.1.into_static(),)
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_kept_lifetime() {
+        let input = quote! {
+            #[lifetime(keep = "'arena")]
+            struct Node<'arena, 'src> {
+                name: Cow<'src, str>,
+                children: &'arena [Node<'arena, 'static>],
+            }
+        };
+        let expected = quote! {
+            impl<'arena, 'src> Node<'arena, 'src> {
+                fn into_static(self) -> Node<'arena, 'static> {
+                    use ::lifetime::IntoStatic;
+
+                    Node {
+                        name: self.name.into_static(),
+                        children: self.children,
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_kept_lifetime_bound() {
+        let input = quote! {
+            #[lifetime(keep = "'arena")]
+            struct Node<'arena: 'src, 'src>(&'arena str, Cow<'src, str>);
+        };
+        let expected = quote! {
+            impl<'arena: 'src, 'src> Node<'arena, 'src> {
+                fn into_static(self) -> Node<'arena, 'static>
+                where
+                    'arena: 'static
+                {
+                    use ::lifetime::IntoStatic;
+
+                    Node(self.0, self.1.into_static(),)
+                }
+            }
         };
-        derive(parse(input));
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_unknown_kept_lifetime() {
+        let input = quote! {
+            #[lifetime(keep = "'arena")]
+            struct Node<'src>(Cow<'src, str>);
+        };
+        test_derive_input_to_error(input, "only lifetime parameters of the type can be kept");
+    }
+
+    #[test]
+    fn derive_struct_with_kept_lifetime_and_from() {
+        let input = quote! {
+            #[lifetime(keep = "'arena", from)]
+            struct Node<'arena, 'src>(&'arena str, Cow<'src, str>);
+        };
+        test_derive_input_to_error(
+            input,
+            "the lifetime attribute `from` can't be used with `keep`, \
+             because a type with kept lifetimes doesn't implement `IntoStatic`",
+        );
+    }
+
+    #[test]
+    fn derive_struct_with_alias() {
+        let input = quote! {
+            #[lifetime(alias(static = "ExampleOwned", borrowed = "ExampleRef"))]
+            pub(crate) struct Example<'a, T: Clone, const N: usize>([T; N], Cow<'a, str>);
+        };
+        let expected = quote! {
+            impl<'a, T: Clone, const N: usize> ::lifetime::IntoStatic for Example<'a, T, N>
+            where
+                T: ::lifetime::IntoStatic,
+                <T as ::lifetime::IntoStatic>::Static: Clone
+            {
+                type Static = Example<'static, <T as ::lifetime::IntoStatic>::Static, N>;
+
+                fn into_static(self) -> Example<'static, <T as ::lifetime::IntoStatic>::Static, N> {
+                    use ::lifetime::IntoStatic;
+
+                    Example(self.0.into_static(), self.1.into_static(),)
+                }
+            }
+
+            pub(crate) type ExampleOwned<T, const N: usize> =
+                Example<'static, <T as ::lifetime::IntoStatic>::Static, N>;
+
+            impl<'a, T: Clone, const N: usize> ::lifetime::__private::StaticAlias
+                for Example<'a, T, N>
+            {}
+
+            const _: () = {
+                #[allow(dead_code)]
+                fn assert_alias_derived<'a, T: Clone, const N: usize>() {
+                    ::lifetime::__private::assert_borrowed_alias::<Example<'a, T, N>>();
+                }
+            };
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_from() {
+        let input = quote! {
+            #[lifetime(from)]
+            pub struct Example<'a, T: Clone + 'a>(Vec<T>, Cow<'a, str>);
+        };
+        let expected = quote! {
+            impl<'a, T: Clone + 'a> ::lifetime::IntoStatic for Example<'a, T>
+            where
+                T: ::lifetime::IntoStatic,
+                <T as ::lifetime::IntoStatic>::Static: Clone + 'static
+            {
+                type Static = Example<'static, <T as ::lifetime::IntoStatic>::Static>;
+
+                fn into_static(self) -> Example<'static, <T as ::lifetime::IntoStatic>::Static> {
+                    use ::lifetime::IntoStatic;
+
+                    Example(self.0.into_static(), self.1.into_static(),)
+                }
+            }
+
+            impl<T: Clone + 'static> Example<'static, T> {
+                /// Converts a value into this static type, with `IntoStatic::into_static`.
+                pub fn from_borrowed(value: impl ::lifetime::IntoStatic<Static = Self>) -> Self {
+                    ::lifetime::IntoStatic::into_static(value)
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
     }
 
     #[test]
-    #[should_panic]
     fn derive_struct_with_generic_const() {
         let input = quote! {
             struct Example<const N: usize>;
         };
-        derive(parse(input));
+        let expected = quote! {
+            impl<const N: usize> ::lifetime::IntoStatic for Example<N> {
+                type Static = Example<N>;
+
+                fn into_static(self) -> Example<N> {
+                    use ::lifetime::IntoStatic;
+
+                    Example
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_generic_const_param() {
+        let input = quote! {
+            struct Example<'a, const N: usize>([u8; N], Cow<'a, str>);
+        };
+        let expected = quote! {
+            impl<'a, const N: usize> ::lifetime::IntoStatic for Example<'a, N> {
+                type Static = Example<'static, N>;
+
+                fn into_static(self) -> Example<'static, N> {
+                    use ::lifetime::IntoStatic;
+
+                    Example(self.0, self.1.into_static(),)
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
     }
 
     #[test]
-    #[should_panic]
     fn derive_struct_with_lifetime_constrains() {
         let input = quote! {
             struct Example<'a, 'b: 'a>(Cow<'a, str>, Cow<'b, str>);
         };
-        derive(parse(input));
+        let expected = quote! {
+            impl<'a, 'b: 'a> ::lifetime::IntoStatic for Example<'a, 'b> {
+                type Static = Example<'static, 'static>;
+
+                fn into_static(self) -> Example<'static, 'static> {
+                    use ::lifetime::IntoStatic;
+
+                    Example(self.0.into_static(), self.1.into_static(),)
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_field_attributes() {
+        let input = quote! {
+            struct Example<'a> {
+                #[lifetime(with = "custom_cow")]
+                custom: Cow<'a, str>,
+                #[lifetime(into_static_with = "one_way_into_static")]
+                one_way: Cow<'a, str>,
+                #[lifetime(default)]
+                cache: Option<Cow<'a, str>>,
+                #[lifetime(clone)]
+                cloned: String,
+            }
+        };
+        let expected = quote! {
+            impl<'a> ::lifetime::IntoStatic for Example<'a> {
+                type Static = Example<'static>;
+
+                fn into_static(self) -> Example<'static> {
+                    use ::lifetime::IntoStatic;
+
+                    Example {
+                        custom: custom_cow::into_static(self.custom),
+                        one_way: one_way_into_static(self.one_way),
+                        cache: {
+                            let _ = self.cache;
+                            ::core::default::Default::default()
+                        },
+                        cloned: self.cloned,
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_conversion_attributes() {
+        let input = quote! {
+            struct Example<'a> {
+                #[lifetime(convert)]
+                name: cow_str!('a),
+                #[lifetime(copy)]
+                callback: fn(&'a str) -> usize,
+            }
+        };
+        let expected = quote! {
+            impl<'a> ::lifetime::IntoStatic for Example<'a> {
+                type Static = Example<'static>;
+
+                fn into_static(self) -> Example<'static> {
+                    use ::lifetime::IntoStatic;
+
+                    Example {
+                        name: self.name.into_static(),
+                        callback: self.callback,
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_conflicting_field_attributes() {
+        let input = quote! {
+            struct Example<'a> {
+                #[lifetime(default, with = "custom_cow")]
+                cow: Cow<'a, str>,
+            }
+        };
+        test_derive_input_to_error(
+            input,
+            "only one of the lifetime attributes `with`, `into_static_with`/`to_borrowed_with`, \
+             `default`/`skip`, `clone`, `convert` and `copy` can be used on a field",
+        );
+    }
+
+    #[test]
+    fn derive_struct_with_unknown_field_attribute() {
+        let input = quote! {
+            struct Example<'a> {
+                #[lifetime(unknown)]
+                cow: Cow<'a, str>,
+            }
+        };
+        test_derive_input_to_error(input, "unknown lifetime attribute");
+    }
+
+    #[test]
+    fn derive_struct_with_crate_path() {
+        let input = quote! {
+            #[lifetime(crate = "facade::lifetime")]
+            struct Example<'a>(Cow<'a, str>);
+        };
+        let expected = quote! {
+            impl<'a> facade::lifetime::IntoStatic for Example<'a> {
+                type Static = Example<'static>;
+
+                fn into_static(self) -> Example<'static> {
+                    use facade::lifetime::IntoStatic;
+
+                    Example(self.0.into_static(),)
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_remote_enum() {
+        let input = quote! {
+            #[lifetime(remote = "other::Token")]
+            pub enum TokenDef<'a> {
+                Word(Cow<'a, str>),
+                Span { source: Cow<'a, str>, start: usize },
+            }
+        };
+        let expected = quote! {
+            impl<'a> TokenDef<'a> {
+                pub fn into_static(value: other::Token<'a>) -> other::Token<'static> {
+                    use ::lifetime::IntoStatic;
+
+                    match value {
+                        other::Token::Word(x0,) => other::Token::Word(x0.into_static(),),
+                        other::Token::Span { source, start, } => other::Token::Span {
+                            source: source.into_static(),
+                            start: start,
+                        },
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_remote_with_from() {
+        let input = quote! {
+            #[lifetime(remote = "other::Span", from)]
+            struct SpanDef<'a>(Cow<'a, str>);
+        };
+        test_derive_input_to_error(
+            input,
+            "the lifetime attribute `from` can't be used with `remote`, \
+             because the conversions would be implemented for a foreign type",
+        );
+    }
+
+    #[test]
+    fn derive_struct_with_unknown_container_attribute() {
+        let input = quote! {
+            #[lifetime(crate = "facade::lifetime", default)]
+            struct Example<'a>(Cow<'a, str>);
+        };
+        test_derive_input_to_error(input, "unknown lifetime attribute");
+    }
+
+    #[test]
+    fn derive_union() {
+        let input = quote! {
+            union Example {
+                number: usize,
+            }
+        };
+        test_derive_input_to_error(
+            input,
+            "unions are not supported, only structs and enums are supported",
+        );
+    }
+
+    #[test]
+    fn derive_struct_with_function_pointer() {
+        let input = quote! {
+            struct Example<'a>(fn(&'a str));
+        };
+        test_derive_input_to_error(
+            input,
+            "function pointers with generic lifetimes can't be converted, \
+             because a function can't be changed to accept or return data with another lifetime",
+        );
+    }
+
+    #[test]
+    fn derive_struct_with_higher_ranked_function_pointer() {
+        let input = quote! {
+            struct Example<'a> {
+                callback: for<'x> fn(&'x str) -> usize,
+                name: Cow<'a, str>,
+            }
+        };
+        let expected = quote! {
+            impl<'a> ::lifetime::IntoStatic for Example<'a> {
+                type Static = Example<'static>;
+
+                fn into_static(self) -> Example<'static> {
+                    use ::lifetime::IntoStatic;
+
+                    Example {
+                        callback: self.callback,
+                        name: self.name.into_static(),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_enum_with_trait_object() {
+        let input = quote! {
+            enum Example<'a> {
+                Boxed(Box<dyn Display + 'a>),
+            }
+        };
+        test_derive_input_to_error(
+            input,
+            "trait objects with generic lifetimes can't be converted, \
+             because the type behind the trait object is unknown",
+        );
     }
 }
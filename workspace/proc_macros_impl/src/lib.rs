@@ -1,5 +1,7 @@
 #![forbid(unsafe_code)]
 
+mod alias;
+mod attr;
 mod generics;
 mod ident;
 pub mod into_static;
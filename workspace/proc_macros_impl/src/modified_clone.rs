@@ -1,109 +1,127 @@
 use crate::ident::{tuple_field_ident, EnumVariantIdent};
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{punctuated::Punctuated, token::Comma, Data, DataEnum, Field, Fields, Ident, Variant};
+use syn::{
+    punctuated::Punctuated, token::Comma, Data, DataEnum, Error, Field, Fields, Path, Variant,
+};
 
 pub(crate) struct ModifiedClone<'a> {
-    pub ident: &'a Ident,
+    /// Path of the constructed type, the derive input itself or a remote type.
+    pub path: &'a Path,
+    /// The value which is cloned, like `self`.
+    pub receiver: &'a TokenStream,
     pub data: &'a Data,
-    pub struct_field_init: &'a FieldInit,
-    pub enum_field_init: &'a FieldInit,
+    pub struct_field_init: &'a FieldInit<'a>,
+    pub enum_field_init: &'a FieldInit<'a>,
 }
 
-type FieldInit = dyn Fn(usize, &Field) -> TokenStream;
+type FieldInit<'a> = dyn Fn(usize, &Field) -> syn::Result<TokenStream> + 'a;
 
 impl<'a> ModifiedClone<'a> {
-    pub(crate) fn expression(self) -> TokenStream {
+    pub(crate) fn expression(self) -> syn::Result<TokenStream> {
         match self.data {
             Data::Struct(struct_data) => {
-                struct_constructor_call(self.ident, &struct_data.fields, self.struct_field_init)
+                struct_constructor_call(self.path, &struct_data.fields, self.struct_field_init)
             }
-            Data::Enum(enum_data) => {
-                matched_enum_constructor_call(self.ident, enum_data, self.enum_field_init)
-            }
-            Data::Union(_) => panic!("only structs and enums are supported"),
+            Data::Enum(enum_data) => matched_enum_constructor_call(
+                self.path,
+                self.receiver,
+                enum_data,
+                self.enum_field_init,
+            ),
+            Data::Union(union_data) => Err(Error::new_spanned(
+                union_data.union_token,
+                "unions are not supported, only structs and enums are supported",
+            )),
         }
     }
 }
 
 fn struct_constructor_call(
-    ident: &Ident,
+    path: &Path,
     fields: &Fields,
     struct_field_init: &FieldInit,
-) -> TokenStream {
+) -> syn::Result<TokenStream> {
     match fields {
         Fields::Named(named_fields) => {
             let fields_initialization =
-                struct_fields_initialization(&named_fields.named, struct_field_init);
-            quote! {
-                #ident { #fields_initialization }
-            }
+                struct_fields_initialization(&named_fields.named, struct_field_init)?;
+            Ok(quote! {
+                #path { #fields_initialization }
+            })
         }
         Fields::Unnamed(unnamed_fields) => {
             let fields_initialization =
-                struct_fields_initialization(&unnamed_fields.unnamed, struct_field_init);
-            quote! {
-                #ident(#fields_initialization)
-            }
+                struct_fields_initialization(&unnamed_fields.unnamed, struct_field_init)?;
+            Ok(quote! {
+                #path(#fields_initialization)
+            })
         }
-        Fields::Unit => panic!("unit structs are not supported"),
+        Fields::Unit => Ok(quote! {
+            #path
+        }),
     }
 }
 
 fn struct_fields_initialization(
     fields: &Punctuated<Field, Comma>,
     struct_field_init: &FieldInit,
-) -> TokenStream {
-    fields
-        .iter()
-        .enumerate()
-        .map(|(index, field)| struct_field_init(index, field))
-        .collect()
+) -> syn::Result<TokenStream> {
+    collect_combining_errors(
+        fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| struct_field_init(index, field)),
+    )
 }
 
 fn matched_enum_constructor_call(
-    enum_ident: &Ident,
+    enum_path: &Path,
+    receiver: &TokenStream,
     enum_data: &DataEnum,
     enum_field_init: &FieldInit,
-) -> TokenStream {
-    let patterns_and_construction: TokenStream = enum_data
-        .variants
-        .iter()
-        .map(|variant| variant_pattern_and_construction(enum_ident, variant, enum_field_init))
-        .collect();
-    quote! {
-        match self {
+) -> syn::Result<TokenStream> {
+    let patterns_and_construction = collect_combining_errors(
+        enum_data
+            .variants
+            .iter()
+            .map(|variant| variant_pattern_and_construction(enum_path, variant, enum_field_init)),
+    )?;
+    Ok(quote! {
+        match #receiver {
             #patterns_and_construction
         }
-    }
+    })
 }
 
 fn variant_pattern_and_construction(
-    enum_ident: &Ident,
+    enum_path: &Path,
     variant: &Variant,
     enum_field_init: &FieldInit,
-) -> TokenStream {
+) -> syn::Result<TokenStream> {
     let ident = EnumVariantIdent {
-        enum_ident: enum_ident.clone(),
+        enum_path: enum_path.clone(),
         variant_ident: variant.ident.clone(),
     };
     match &variant.fields {
         Fields::Named(f) => {
             let enum_fields_pattern = enum_fields_pattern(&f.named);
-            let enum_fields_initialization = enum_fields_initialization(&f.named, enum_field_init);
-            quote! {
+            let enum_fields_initialization = enum_fields_initialization(&f.named, enum_field_init)?;
+            Ok(quote! {
                 #ident { #enum_fields_pattern } => #ident { #enum_fields_initialization },
-            }
+            })
         }
         Fields::Unnamed(f) => {
             let enum_fields_pattern = enum_fields_pattern(&f.unnamed);
             let enum_fields_initialization =
-                enum_fields_initialization(&f.unnamed, enum_field_init);
-            quote! {
+                enum_fields_initialization(&f.unnamed, enum_field_init)?;
+            Ok(quote! {
                 #ident ( #enum_fields_pattern ) => #ident ( #enum_fields_initialization ),
-            }
+            })
         }
-        Fields::Unit => todo!(),
+        Fields::Unit => Ok(quote! {
+            #ident => #ident,
+        }),
     }
 }
 
@@ -132,10 +150,31 @@ fn enum_field_pattern(index: usize, field: &Field) -> TokenStream {
 fn enum_fields_initialization(
     fields: &Punctuated<Field, Comma>,
     enum_field_init: &FieldInit,
-) -> TokenStream {
-    fields
-        .iter()
-        .enumerate()
-        .map(|(index, field)| enum_field_init(index, field))
-        .collect()
+) -> syn::Result<TokenStream> {
+    collect_combining_errors(
+        fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| enum_field_init(index, field)),
+    )
+}
+
+/// Collects all tokens, or all errors combined into one if there are any.
+fn collect_combining_errors(
+    results: impl Iterator<Item = syn::Result<TokenStream>>,
+) -> syn::Result<TokenStream> {
+    let mut tokens = TokenStream::new();
+    let mut error: Option<Error> = None;
+    for result in results {
+        match (result, &mut error) {
+            (Ok(result_tokens), None) => tokens.extend(result_tokens),
+            (Ok(_), Some(_)) => {}
+            (Err(result_error), Some(error)) => error.combine(result_error),
+            (Err(result_error), None) => error = Some(result_error),
+        }
+    }
+    match error {
+        Some(error) => Err(error),
+        None => Ok(tokens),
+    }
 }
@@ -1,98 +1,220 @@
 use crate::{
-    generics::{add_lifetime, assert_generics_are_supported, replace_lifetimes},
-    ident::tuple_field_ident,
+    alias::{alias_marker_impl, assert_alias_derived},
+    attr::{ContainerAttrs, FieldAttrs},
+    generics::{add_lifetime, alias_generics, type_params, Bounds, ReplaceGenerics},
+    ident::{remote_receiver, respan, tuple_field_ident},
     modified_clone::ModifiedClone,
-    type_::type_has_generic_lifetime,
+    type_::{
+        assert_no_reference_to_type_param, assert_type_is_supported, field_needs_conversion,
+        ConvertedParams,
+    },
 };
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use std::convert::TryFrom;
-use syn::{DeriveInput, Field, Index, Lifetime};
+use syn::{
+    parse_quote, spanned::Spanned, DeriveInput, Error, Field, Ident, Index, Lifetime, Path, Type,
+};
 
-pub fn derive(input: DeriveInput) -> TokenStream {
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
     let ref_lifetime = Lifetime::new("'ref_", Span::mixed_site());
+    let attrs = ContainerAttrs::from_input(&input)?;
+    let crate_path = &attrs.crate_path;
     let generics = input.generics;
-    assert_generics_are_supported(&generics);
     let all_generics = add_lifetime(generics.clone(), ref_lifetime.clone());
-    let borrowed_generics = replace_lifetimes(generics.clone(), &ref_lifetime);
     let ident = input.ident;
+    let (impl_generics, _, _) = all_generics.split_for_impl();
+    let (input_impl_generics, ty_generics, input_where_clause) = generics.split_for_impl();
+    let (path, receiver) = match &attrs.remote {
+        Some(remote) => (remote.clone(), remote_receiver()),
+        None => (Path::from(ident.clone()), quote!(self)),
+    };
+    let self_ty: Type = parse_quote!(#path #ty_generics);
+    let borrowed_param =
+        |param: &Ident| parse_quote!(<&#ref_lifetime #param as #crate_path::ToBorrowed>::Borrowed);
+    let mut replace_generics =
+        ReplaceGenerics::new(&generics, &attrs.keep, &ref_lifetime, &borrowed_param);
+    let borrowed_ty = replace_generics.ty(&self_ty);
+    let bounds = Bounds::new(
+        &generics,
+        &input.data,
+        attrs.bounds.custom_to_borrowed(),
+        &|field_attrs| field_attrs.bounds.custom_to_borrowed().map(<[_]>::to_vec),
+    )?;
+    let where_clause = replace_generics.where_clause(
+        &generics,
+        &bounds,
+        &|param| parse_quote!(for<'t> &'t #param: #crate_path::ToBorrowed),
+    );
+    let params = ConvertedParams {
+        type_params: type_params(&generics),
+        kept_lifetimes: attrs.keep.clone(),
+    };
     let fn_body = ModifiedClone {
-        ident: &ident,
+        path: &path,
+        receiver: &receiver,
         data: &input.data,
-        struct_field_init: &struct_field_initialization,
-        enum_field_init: &enum_field_initialization,
+        struct_field_init: &|index, field| {
+            struct_field_initialization(index, field, &receiver, &params, &bounds, crate_path)
+        },
+        enum_field_init: &|index, field| {
+            enum_field_initialization(index, field, &params, &bounds, crate_path)
+        },
     }
-    .expression();
-    quote! {
-        impl #all_generics lifetime::ToBorrowed for & #ref_lifetime #ident #generics {
-            type Borrowed = #ident #borrowed_generics;
-
-            fn to_borrowed(self) -> #ident #borrowed_generics {
-                use lifetime::ToBorrowed;
+    .expression()?;
+    let vis = &input.vis;
+    let conversion = if attrs.remote.is_some() {
+        // The type parameters of the remote type would have to be inferred
+        // from the borrowed type, like with the `from` attribute.
+        if let Some(type_param) = generics.type_params().next() {
+            return Err(Error::new_spanned(
+                type_param,
+                "the lifetime attribute `remote` can't be used with type parameters, \
+                 because the type to borrow from can't be inferred from the borrowed type",
+            ));
+        }
+        quote! {
+            impl #input_impl_generics #ident #ty_generics #input_where_clause {
+                #vis fn to_borrowed<#ref_lifetime>(
+                    #receiver: & #ref_lifetime #self_ty,
+                ) -> #borrowed_ty #where_clause {
+                    use #crate_path::ToBorrowed;
 
-                #fn_body
+                    #fn_body
+                }
             }
         }
-    }
-}
+    } else {
+        quote! {
+            impl #impl_generics #crate_path::ToBorrowed for & #ref_lifetime #self_ty #where_clause {
+                type Borrowed = #borrowed_ty;
 
-fn struct_field_initialization(index: usize, field: &Field) -> TokenStream {
-    match &field.ident {
-        Some(ident) => {
-            if type_has_generic_lifetime(&field.ty) {
-                quote! {
-                    #ident: self.#ident.to_borrowed(),
-                }
-            } else {
-                quote! {
-                    #ident: self.#ident,
+                fn to_borrowed(self) -> #borrowed_ty {
+                    use #crate_path::ToBorrowed;
+
+                    #fn_body
                 }
             }
         }
+    };
+    let alias = attrs.borrowed_alias.as_ref().map(|alias| {
+        let alias_generics =
+            add_lifetime(alias_generics(&generics, &attrs.keep), ref_lifetime.clone());
+        let (alias_params, _, _) = alias_generics.split_for_impl();
+        let marker_impl = alias_marker_impl(crate_path, "BorrowedAlias", &ident, &generics);
+        quote! {
+            #vis type #alias #alias_params = #borrowed_ty;
+            #marker_impl
+        }
+    });
+    let static_alias_assertion = attrs.static_alias.as_ref().map(|alias| {
+        assert_alias_derived(crate_path, "assert_static_alias", alias, &ident, &generics)
+    });
+    let from = if attrs.from {
+        // With type parameters, `Example::from(&example)` would have to infer the type parameters
+        // of the argument from the borrowed ones, which overflows for recursive types like `Cow`.
+        if let Some(type_param) = generics.type_params().next() {
+            return Err(Error::new_spanned(
+                type_param,
+                "the lifetime attribute `from` can't be used with type parameters, \
+                 because the type to borrow from can't be inferred from the borrowed type",
+            ));
+        }
+        Some(quote! {
+            impl #impl_generics ::core::convert::From<& #ref_lifetime #self_ty> for #borrowed_ty
+            #where_clause
+            {
+                fn from(value: & #ref_lifetime #self_ty) -> Self {
+                    #crate_path::ToBorrowed::to_borrowed(value)
+                }
+            }
+        })
+    } else {
+        None
+    };
+    Ok(quote! {
+        #conversion
+        #from
+        #alias
+        #static_alias_assertion
+    })
+}
+
+fn struct_field_initialization(
+    index: usize,
+    field: &Field,
+    receiver: &TokenStream,
+    params: &ConvertedParams,
+    bounds: &Bounds,
+    crate_path: &Path,
+) -> syn::Result<TokenStream> {
+    let value = match &field.ident {
+        Some(ident) => quote!(#receiver.#ident),
         None => {
             let index = Index {
                 index: u32::try_from(index).unwrap(),
                 span: Span::mixed_site(),
             };
-            if type_has_generic_lifetime(&field.ty) {
-                quote! {
-                    self.#index.to_borrowed(),
-                }
-            } else {
-                quote! {
-                    self.#index,
-                }
-            }
+            quote!(#receiver.#index)
         }
-    }
+    };
+    field_initialization(field, quote!(&#value), params, bounds, crate_path)
 }
 
-fn enum_field_initialization(index: usize, field: &Field) -> TokenStream {
-    match &field.ident {
-        Some(ident) => {
-            if type_has_generic_lifetime(&field.ty) {
-                quote! {
-                    #ident: #ident.to_borrowed(),
-                }
-            } else {
-                quote! {
-                    #ident: *#ident,
-                }
-            }
-        }
+fn enum_field_initialization(
+    index: usize,
+    field: &Field,
+    params: &ConvertedParams,
+    bounds: &Bounds,
+    crate_path: &Path,
+) -> syn::Result<TokenStream> {
+    let reference = match &field.ident {
+        Some(ident) => quote!(#ident),
         None => {
             let tuple_field_ident = tuple_field_ident(index);
-            if type_has_generic_lifetime(&field.ty) {
-                quote! {
-                    #tuple_field_ident.to_borrowed(),
-                }
-            } else {
-                quote! {
-                    *#tuple_field_ident,
-                }
-            }
+            quote!(#tuple_field_ident)
         }
-    }
+    };
+    field_initialization(field, reference, params, bounds, crate_path)
+}
+
+/// Initializes a field of the borrowed value from a `reference` to the field of `self`.
+///
+/// Fields which aren't converted are copied,
+/// with an error pointing at the field if it isn't `Copy`.
+fn field_initialization(
+    field: &Field,
+    reference: TokenStream,
+    params: &ConvertedParams,
+    bounds: &Bounds,
+    crate_path: &Path,
+) -> syn::Result<TokenStream> {
+    let attrs = FieldAttrs::from_field(field)?;
+    let borrowed_value = if let Some(to_borrowed_with) = attrs.custom_to_borrowed() {
+        quote!(#to_borrowed_with(#reference))
+    } else if attrs.default {
+        quote!({
+            let _ = #reference;
+            ::core::default::Default::default()
+        })
+    } else if attrs.clone {
+        quote!(::core::clone::Clone::clone(#reference))
+    } else if !attrs.copy && (attrs.convert || field_needs_conversion(field, params)) {
+        assert_type_is_supported(&field.ty, &params.kept_lifetimes)?;
+        assert_no_reference_to_type_param(&field.ty, bounds.inferred_params())?;
+        quote!(ToBorrowed::to_borrowed(#reference))
+    } else {
+        let span = field
+            .ident
+            .as_ref()
+            .map_or_else(|| field.ty.span(), Ident::span);
+        let reference = respan(reference, span);
+        quote_spanned!(span=> #crate_path::__private::copy_field(#reference))
+    };
+    Ok(match &field.ident {
+        Some(ident) => quote!(#ident: #borrowed_value,),
+        None => quote!(#borrowed_value,),
+    })
 }
 
 #[cfg(test)]
@@ -100,9 +222,14 @@ mod tests {
     use super::*;
 
     fn test_derive_input_to_output(input: TokenStream, expected: TokenStream) {
-        let actual = derive(parse(input));
+        let actual = derive(parse(input)).unwrap();
         println!("{:#}", actual);
-        assert_eq!(parse::<syn::Item>(actual), parse::<syn::Item>(expected),);
+        assert_eq!(parse::<syn::File>(actual), parse::<syn::File>(expected),);
+    }
+
+    fn test_derive_input_to_error(input: TokenStream, expected: &str) {
+        let actual = derive(parse(input)).unwrap_err();
+        assert_eq!(actual.to_string(), expected);
     }
 
     #[track_caller]
@@ -119,15 +246,15 @@ mod tests {
             }
         };
         let expected = quote! {
-            impl<'ref_, 'a> lifetime::ToBorrowed for &'ref_ Example<'a> {
+            impl<'ref_, 'a> ::lifetime::ToBorrowed for &'ref_ Example<'a> {
                 type Borrowed = Example<'ref_>;
 
                 fn to_borrowed(self) -> Example<'ref_> {
-                    use lifetime::ToBorrowed;
+                    use ::lifetime::ToBorrowed;
 
                     Example {
-                        primitive: self.primitive,
-                        cow: self.cow.to_borrowed(),
+                        primitive: ::lifetime::__private::copy_field(&self.primitive),
+                        cow: ToBorrowed::to_borrowed(&self.cow),
                     }
                 }
             }
@@ -141,13 +268,13 @@ mod tests {
             struct Example<'a>(usize, Cow<'a, str>);
         };
         let expected = quote! {
-            impl<'ref_, 'a> lifetime::ToBorrowed for &'ref_ Example<'a> {
+            impl<'ref_, 'a> ::lifetime::ToBorrowed for &'ref_ Example<'a> {
                 type Borrowed = Example<'ref_>;
 
                 fn to_borrowed(self) -> Example<'ref_> {
-                    use lifetime::ToBorrowed;
+                    use ::lifetime::ToBorrowed;
 
-                    Example(self.0, self.1.to_borrowed(),)
+                    Example(::lifetime::__private::copy_field(&self.0), ToBorrowed::to_borrowed(&self.1),)
                 }
             }
         };
@@ -169,17 +296,46 @@ mod tests {
             }
         };
         let expected = quote! {
-            impl<'ref_, 'a> lifetime::ToBorrowed for &'ref_ Example<'a> {
+            impl<'ref_, 'a> ::lifetime::ToBorrowed for &'ref_ Example<'a> {
                 type Borrowed = Example<'ref_>;
 
                 fn to_borrowed(self) -> Example<'ref_> {
-                    use lifetime::ToBorrowed;
+                    use ::lifetime::ToBorrowed;
 
                     match self {
-                        Example::Primitive0 { number, } => Example::Primitive0 { number: *number, },
-                        Example::Primitive1(x0,) => Example::Primitive1(*x0,),
-                        Example::Cow0 { string, } => Example::Cow0 { string: string.to_borrowed(), },
-                        Example::Cow1(x0,) => Example::Cow1(x0.to_borrowed(),),
+                        Example::Primitive0 { number, } => Example::Primitive0 { number: ::lifetime::__private::copy_field(number), },
+                        Example::Primitive1(x0,) => Example::Primitive1(::lifetime::__private::copy_field(x0),),
+                        Example::Cow0 { string, } => Example::Cow0 { string: ToBorrowed::to_borrowed(string), },
+                        Example::Cow1(x0,) => Example::Cow1(ToBorrowed::to_borrowed(x0),),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_enum_with_empty_variants() {
+        let input = quote! {
+            enum Example<'a> {
+                Unit,
+                EmptyNamed {},
+                EmptyTuple(),
+                Cow(Cow<'a, str>),
+            }
+        };
+        let expected = quote! {
+            impl<'ref_, 'a> ::lifetime::ToBorrowed for &'ref_ Example<'a> {
+                type Borrowed = Example<'ref_>;
+
+                fn to_borrowed(self) -> Example<'ref_> {
+                    use ::lifetime::ToBorrowed;
+
+                    match self {
+                        Example::Unit => Example::Unit,
+                        Example::EmptyNamed {} => Example::EmptyNamed {},
+                        Example::EmptyTuple() => Example::EmptyTuple(),
+                        Example::Cow(x0,) => Example::Cow(ToBorrowed::to_borrowed(x0),),
                     }
                 }
             }
@@ -193,13 +349,13 @@ mod tests {
             struct Example<'a>(&'static Location<'static>, Cow<'a, str>);
         };
         let expected = quote! {
-            impl<'ref_, 'a> lifetime::ToBorrowed for &'ref_ Example<'a> {
+            impl<'ref_, 'a> ::lifetime::ToBorrowed for &'ref_ Example<'a> {
                 type Borrowed = Example<'ref_>;
 
                 fn to_borrowed(self) -> Example<'ref_> {
-                    use lifetime::ToBorrowed;
+                    use ::lifetime::ToBorrowed;
 
-                    Example(self.0, self.1.to_borrowed(),)
+                    Example(::lifetime::__private::copy_field(&self.0), ToBorrowed::to_borrowed(&self.1),)
                 }
             }
         };
@@ -207,29 +363,539 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
     fn derive_struct_with_generic_type() {
         let input = quote! {
-            struct Example<T>(T);
+            struct Example<'a, T: Clone>(Vec<T>, Cow<'a, str>, usize)
+            where
+                T: Debug;
+        };
+        let expected = quote! {
+            impl<'ref_, 'a, T: Clone> ::lifetime::ToBorrowed for &'ref_ Example<'a, T>
+            where
+                T: Debug,
+                for<'t> &'t T: ::lifetime::ToBorrowed,
+                <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed: Clone,
+                <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed: Debug
+            {
+                type Borrowed = Example<'ref_, <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed>;
+
+                fn to_borrowed(self) -> Example<'ref_, <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed> {
+                    use ::lifetime::ToBorrowed;
+
+                    Example(
+                        ToBorrowed::to_borrowed(&self.0),
+                        ToBorrowed::to_borrowed(&self.1),
+                        ::lifetime::__private::copy_field(&self.2),
+                    )
+                }
+            }
         };
-        derive(parse(input));
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_field_bound() {
+        let input = quote! {
+            #[lifetime(into_static_bound = "T: Send")]
+            struct Example<'a, T: Clone, U> {
+                #[lifetime(bound = "for<'t> &'t T: ToBorrowed<Borrowed = T>")]
+                items: Vec<T>,
+                item: Option<U>,
+                cow: Cow<'a, str>,
+            }
+        };
+        let expected = quote! {
+            impl<'ref_, 'a, T: Clone, U> ::lifetime::ToBorrowed for &'ref_ Example<'a, T, U>
+            where
+                <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed: Clone,
+                for<'t> &'t U: ::lifetime::ToBorrowed,
+                for<'t> &'t T: ToBorrowed<Borrowed = T>
+            {
+                type Borrowed = Example<
+                    'ref_,
+                    <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed,
+                    <&'ref_ U as ::lifetime::ToBorrowed>::Borrowed
+                >;
+
+                fn to_borrowed(self) -> Example<
+                    'ref_,
+                    <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed,
+                    <&'ref_ U as ::lifetime::ToBorrowed>::Borrowed
+                > {
+                    use ::lifetime::ToBorrowed;
+
+                    Example {
+                        items: ToBorrowed::to_borrowed(&self.items),
+                        item: ToBorrowed::to_borrowed(&self.item),
+                        cow: ToBorrowed::to_borrowed(&self.cow),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_kept_lifetime() {
+        let input = quote! {
+            #[lifetime(keep = "'arena")]
+            struct Node<'arena, 'src> {
+                name: Cow<'src, str>,
+                children: &'arena [Node<'arena, 'static>],
+            }
+        };
+        let expected = quote! {
+            impl<'ref_, 'arena, 'src> ::lifetime::ToBorrowed for &'ref_ Node<'arena, 'src> {
+                type Borrowed = Node<'arena, 'ref_>;
+
+                fn to_borrowed(self) -> Node<'arena, 'ref_> {
+                    use ::lifetime::ToBorrowed;
+
+                    Node {
+                        name: ToBorrowed::to_borrowed(&self.name),
+                        children: ::lifetime::__private::copy_field(&self.children),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_kept_lifetime_bound() {
+        let input = quote! {
+            #[lifetime(keep = "'arena")]
+            struct Node<'arena, 'src: 'arena> {
+                name: Cow<'src, str>,
+                parent: Option<&'arena Node<'arena, 'src>>,
+            }
+        };
+        let expected = quote! {
+            impl<'ref_, 'arena, 'src: 'arena> ::lifetime::ToBorrowed for &'ref_ Node<'arena, 'src>
+            where
+                'ref_: 'arena
+            {
+                type Borrowed = Node<'arena, 'ref_>;
+
+                fn to_borrowed(self) -> Node<'arena, 'ref_> {
+                    use ::lifetime::ToBorrowed;
+
+                    Node {
+                        name: ToBorrowed::to_borrowed(&self.name),
+                        parent: ToBorrowed::to_borrowed(&self.parent),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_alias() {
+        let input = quote! {
+            #[lifetime(keep = "'arena", alias(static = "NodeOwned", borrowed = "NodeRef"))]
+            pub struct Node<'arena, 'src, T> {
+                name: Cow<'src, str>,
+                children: &'arena [u32],
+                values: Vec<T>,
+            }
+        };
+        let expected = quote! {
+            impl<'ref_, 'arena, 'src, T> ::lifetime::ToBorrowed for &'ref_ Node<'arena, 'src, T>
+            where
+                for<'t> &'t T: ::lifetime::ToBorrowed
+            {
+                type Borrowed = Node<'arena, 'ref_, <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed>;
+
+                fn to_borrowed(self) -> Node<'arena, 'ref_, <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed> {
+                    use ::lifetime::ToBorrowed;
+
+                    Node {
+                        name: ToBorrowed::to_borrowed(&self.name),
+                        children: ::lifetime::__private::copy_field(&self.children),
+                        values: ToBorrowed::to_borrowed(&self.values),
+                    }
+                }
+            }
+
+            pub type NodeRef<'ref_, 'arena, T> =
+                Node<'arena, 'ref_, <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed>;
+
+            impl<'arena, 'src, T> ::lifetime::__private::BorrowedAlias for Node<'arena, 'src, T> {}
+
+            const _: () = {
+                #[allow(dead_code)]
+                fn assert_alias_derived<'arena, 'src, T>() {
+                    ::lifetime::__private::assert_static_alias::<Node<'arena, 'src, T>>();
+                }
+            };
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_from() {
+        let input = quote! {
+            #[lifetime(from)]
+            struct Example<'a>(Vec<u32>, Cow<'a, str>);
+        };
+        let expected = quote! {
+            impl<'ref_, 'a> ::lifetime::ToBorrowed for &'ref_ Example<'a> {
+                type Borrowed = Example<'ref_>;
+
+                fn to_borrowed(self) -> Example<'ref_> {
+                    use ::lifetime::ToBorrowed;
+
+                    Example(
+                        ::lifetime::__private::copy_field(&self.0),
+                        ToBorrowed::to_borrowed(&self.1),
+                    )
+                }
+            }
+
+            impl<'ref_, 'a> ::core::convert::From<&'ref_ Example<'a>> for Example<'ref_> {
+                fn from(value: &'ref_ Example<'a>) -> Self {
+                    ::lifetime::ToBorrowed::to_borrowed(value)
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_from_and_type_param() {
+        let input = quote! {
+            #[lifetime(from)]
+            struct Example<'a, T>(Vec<T>, Cow<'a, str>);
+        };
+        test_derive_input_to_error(
+            input,
+            "the lifetime attribute `from` can't be used with type parameters, \
+             because the type to borrow from can't be inferred from the borrowed type",
+        );
     }
 
     #[test]
-    #[should_panic]
     fn derive_struct_with_generic_const() {
         let input = quote! {
             struct Example<const N: usize>;
         };
-        derive(parse(input));
+        let expected = quote! {
+            impl<'ref_, const N: usize> ::lifetime::ToBorrowed for &'ref_ Example<N> {
+                type Borrowed = Example<N>;
+
+                fn to_borrowed(self) -> Example<N> {
+                    use ::lifetime::ToBorrowed;
+
+                    Example
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_generic_const_param() {
+        let input = quote! {
+            struct Example<'a, const N: usize>([u8; N], Cow<'a, str>);
+        };
+        let expected = quote! {
+            impl<'ref_, 'a, const N: usize> ::lifetime::ToBorrowed for &'ref_ Example<'a, N> {
+                type Borrowed = Example<'ref_, N>;
+
+                fn to_borrowed(self) -> Example<'ref_, N> {
+                    use ::lifetime::ToBorrowed;
+
+                    Example(::lifetime::__private::copy_field(&self.0), ToBorrowed::to_borrowed(&self.1),)
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
     }
 
     #[test]
-    #[should_panic]
     fn derive_struct_with_lifetime_constrains() {
         let input = quote! {
             struct Example<'a, 'b: 'a>(Cow<'a, str>, Cow<'b, str>);
         };
-        derive(parse(input));
+        let expected = quote! {
+            impl<'ref_, 'a, 'b: 'a> ::lifetime::ToBorrowed for &'ref_ Example<'a, 'b> {
+                type Borrowed = Example<'ref_, 'ref_>;
+
+                fn to_borrowed(self) -> Example<'ref_, 'ref_> {
+                    use ::lifetime::ToBorrowed;
+
+                    Example(
+                        ToBorrowed::to_borrowed(&self.0),
+                        ToBorrowed::to_borrowed(&self.1),
+                    )
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_field_attributes() {
+        let input = quote! {
+            struct Example<'a> {
+                #[lifetime(with = "custom_cow")]
+                custom: Cow<'a, str>,
+                #[lifetime(into_static_with = "one_way_into_static")]
+                one_way: Cow<'a, str>,
+                #[lifetime(default)]
+                cache: Option<Cow<'a, str>>,
+                #[lifetime(clone)]
+                cloned: String,
+            }
+        };
+        let expected = quote! {
+            impl<'ref_, 'a> ::lifetime::ToBorrowed for &'ref_ Example<'a> {
+                type Borrowed = Example<'ref_>;
+
+                fn to_borrowed(self) -> Example<'ref_> {
+                    use ::lifetime::ToBorrowed;
+
+                    Example {
+                        custom: custom_cow::to_borrowed(&self.custom),
+                        one_way: ToBorrowed::to_borrowed(&self.one_way),
+                        cache: {
+                            let _ = &self.cache;
+                            ::core::default::Default::default()
+                        },
+                        cloned: ::core::clone::Clone::clone(&self.cloned),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_conversion_attributes() {
+        let input = quote! {
+            struct Example<'a> {
+                #[lifetime(convert)]
+                name: cow_str!('a),
+                #[lifetime(copy)]
+                text: &'a str,
+            }
+        };
+        let expected = quote! {
+            impl<'ref_, 'a> ::lifetime::ToBorrowed for &'ref_ Example<'a> {
+                type Borrowed = Example<'ref_>;
+
+                fn to_borrowed(self) -> Example<'ref_> {
+                    use ::lifetime::ToBorrowed;
+
+                    Example {
+                        name: ToBorrowed::to_borrowed(&self.name),
+                        text: ::lifetime::__private::copy_field(&self.text),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_conflicting_field_attributes() {
+        let input = quote! {
+            struct Example<'a> {
+                #[lifetime(default, with = "custom_cow")]
+                cow: Cow<'a, str>,
+            }
+        };
+        test_derive_input_to_error(
+            input,
+            "only one of the lifetime attributes `with`, `into_static_with`/`to_borrowed_with`, \
+             `default`/`skip`, `clone`, `convert` and `copy` can be used on a field",
+        );
+    }
+
+    #[test]
+    fn derive_struct_with_unknown_field_attribute() {
+        let input = quote! {
+            struct Example<'a> {
+                #[lifetime(unknown)]
+                cow: Cow<'a, str>,
+            }
+        };
+        test_derive_input_to_error(input, "unknown lifetime attribute");
+    }
+
+    #[test]
+    fn derive_struct_with_crate_path() {
+        let input = quote! {
+            #[lifetime(crate = "facade::lifetime")]
+            struct Example<'a>(Cow<'a, str>);
+        };
+        let expected = quote! {
+            impl<'ref_, 'a> facade::lifetime::ToBorrowed for &'ref_ Example<'a> {
+                type Borrowed = Example<'ref_>;
+
+                fn to_borrowed(self) -> Example<'ref_> {
+                    use facade::lifetime::ToBorrowed;
+
+                    Example(ToBorrowed::to_borrowed(&self.0),)
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_remote_struct() {
+        let input = quote! {
+            #[lifetime(remote = "other::Span")]
+            struct SpanDef<'a> {
+                source: Cow<'a, str>,
+                start: usize,
+            }
+        };
+        let expected = quote! {
+            impl<'a> SpanDef<'a> {
+                fn to_borrowed<'ref_>(value: &'ref_ other::Span<'a>,) -> other::Span<'ref_> {
+                    use ::lifetime::ToBorrowed;
+
+                    other::Span {
+                        source: ToBorrowed::to_borrowed(&value.source),
+                        start: ::lifetime::__private::copy_field(&value.start),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_remote_with_type_param() {
+        let input = quote! {
+            #[lifetime(remote = "other::Spanned")]
+            struct SpannedDef<'a, T> {
+                value: T,
+                source: Cow<'a, str>,
+            }
+        };
+        test_derive_input_to_error(
+            input,
+            "the lifetime attribute `remote` can't be used with type parameters, \
+             because the type to borrow from can't be inferred from the borrowed type",
+        );
+    }
+
+    #[test]
+    fn derive_struct_with_unknown_container_attribute() {
+        let input = quote! {
+            #[lifetime(crate = "facade::lifetime", default)]
+            struct Example<'a>(Cow<'a, str>);
+        };
+        test_derive_input_to_error(input, "unknown lifetime attribute");
+    }
+
+    #[test]
+    fn derive_union() {
+        let input = quote! {
+            union Example {
+                number: usize,
+            }
+        };
+        test_derive_input_to_error(
+            input,
+            "unions are not supported, only structs and enums are supported",
+        );
+    }
+
+    #[test]
+    fn derive_struct_with_function_pointer() {
+        let input = quote! {
+            struct Example<'a>(fn(&'a str));
+        };
+        test_derive_input_to_error(
+            input,
+            "function pointers with generic lifetimes can't be converted, \
+             because a function can't be changed to accept or return data with another lifetime",
+        );
+    }
+
+    #[test]
+    fn derive_struct_with_higher_ranked_function_pointer() {
+        let input = quote! {
+            struct Example<'a> {
+                callback: for<'x> fn(&'x str) -> usize,
+                name: Cow<'a, str>,
+            }
+        };
+        let expected = quote! {
+            impl<'ref_, 'a> ::lifetime::ToBorrowed for &'ref_ Example<'a> {
+                type Borrowed = Example<'ref_>;
+
+                fn to_borrowed(self) -> Example<'ref_> {
+                    use ::lifetime::ToBorrowed;
+
+                    Example {
+                        callback: ::lifetime::__private::copy_field(&self.callback),
+                        name: ToBorrowed::to_borrowed(&self.name),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_struct_with_reference_to_type_param() {
+        let input = quote! {
+            struct Example<'a, T> {
+                value: &'a T,
+                values: Option<&'a [T]>,
+            }
+        };
+        test_derive_input_to_error(
+            input,
+            "references to type parameters can't be borrowed without a bound attribute, \
+             because the reference keeps the original type instead of the borrowed type",
+        );
+    }
+
+    #[test]
+    fn derive_struct_with_reference_to_bounded_type_param() {
+        let input = quote! {
+            struct Example<'a, T> {
+                #[lifetime(bound = "for<'t> &'t T: ToBorrowed<Borrowed = T>")]
+                value: &'a T,
+            }
+        };
+        let expected = quote! {
+            impl<'ref_, 'a, T> ::lifetime::ToBorrowed for &'ref_ Example<'a, T>
+            where
+                for<'t> &'t T: ToBorrowed<Borrowed = T>
+            {
+                type Borrowed = Example<'ref_, <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed>;
+
+                fn to_borrowed(self) -> Example<'ref_, <&'ref_ T as ::lifetime::ToBorrowed>::Borrowed> {
+                    use ::lifetime::ToBorrowed;
+
+                    Example {
+                        value: ToBorrowed::to_borrowed(&self.value),
+                    }
+                }
+            }
+        };
+        test_derive_input_to_output(input, expected);
+    }
+
+    #[test]
+    fn derive_enum_with_trait_object() {
+        let input = quote! {
+            enum Example<'a> {
+                Boxed(Box<dyn Display + 'a>),
+            }
+        };
+        test_derive_input_to_error(
+            input,
+            "trait objects with generic lifetimes can't be converted, \
+             because the type behind the trait object is unknown",
+        );
     }
 }
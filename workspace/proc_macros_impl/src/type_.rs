@@ -1,24 +1,191 @@
 use syn::{
     visit::{self, Visit},
-    Lifetime, Type,
+    BoundLifetimes, Error, Field, Ident, Lifetime, TraitBound, Type, TypeBareFn, TypePath,
+    TypeReference, TypeTraitObject,
 };
 
-pub(crate) fn type_has_generic_lifetime(ty: &Type) -> bool {
-    struct Visitor {
+/// The generic parameters of the derive input which are converted.
+pub(crate) struct ConvertedParams {
+    pub type_params: Vec<Ident>,
+    /// The lifetimes which are kept unchanged, with `#[lifetime(keep = "...")]`.
+    pub kept_lifetimes: Vec<Lifetime>,
+}
+
+pub(crate) fn field_needs_conversion(field: &Field, params: &ConvertedParams) -> bool {
+    type_has_generic_lifetime(&field.ty, &params.kept_lifetimes)
+        || type_has_type_param(&field.ty, &params.type_params)
+}
+
+/// Returns an error spanned at every part of the type which can't be converted.
+pub(crate) fn assert_type_is_supported(ty: &Type, kept_lifetimes: &[Lifetime]) -> syn::Result<()> {
+    struct Visitor<'a> {
+        kept_lifetimes: &'a [Lifetime],
+        error: Option<Error>,
+    }
+    impl Visitor<'_> {
+        fn push_error(&mut self, error: Error) {
+            match &mut self.error {
+                Some(existing) => existing.combine(error),
+                None => self.error = Some(error),
+            }
+        }
+    }
+    impl Visit<'_> for Visitor<'_> {
+        fn visit_type_bare_fn(&mut self, bare_fn: &TypeBareFn) {
+            if type_has_generic_lifetime(&Type::BareFn(bare_fn.clone()), self.kept_lifetimes) {
+                self.push_error(Error::new_spanned(
+                    bare_fn,
+                    "function pointers with generic lifetimes can't be converted, \
+                     because a function can't be changed to accept or return data with another lifetime",
+                ));
+            }
+        }
+
+        fn visit_type_trait_object(&mut self, trait_object: &TypeTraitObject) {
+            if type_has_generic_lifetime(
+                &Type::TraitObject(trait_object.clone()),
+                self.kept_lifetimes,
+            ) {
+                self.push_error(Error::new_spanned(
+                    trait_object,
+                    "trait objects with generic lifetimes can't be converted, \
+                     because the type behind the trait object is unknown",
+                ));
+            }
+        }
+    }
+    let mut visitor = Visitor {
+        kept_lifetimes,
+        error: None,
+    };
+    Visit::visit_type(&mut visitor, ty);
+    match visitor.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Returns an error spanned at every reference to one of the `type_params`,
+/// which can't be borrowed, because the `ToBorrowed` impl of a reference copies it,
+/// while the type parameter is replaced by its borrowed type.
+pub(crate) fn assert_no_reference_to_type_param(
+    ty: &Type,
+    type_params: &[Ident],
+) -> syn::Result<()> {
+    struct Visitor<'a> {
+        type_params: &'a [Ident],
+        error: Option<Error>,
+    }
+    impl Visit<'_> for Visitor<'_> {
+        fn visit_type_reference(&mut self, reference: &TypeReference) {
+            if type_has_type_param(&reference.elem, self.type_params) {
+                let error = Error::new_spanned(
+                    reference,
+                    "references to type parameters can't be borrowed without a bound attribute, \
+                     because the reference keeps the original type instead of the borrowed type",
+                );
+                match &mut self.error {
+                    Some(existing) => existing.combine(error),
+                    None => self.error = Some(error),
+                }
+            } else {
+                visit::visit_type_reference(self, reference)
+            }
+        }
+    }
+    let mut visitor = Visitor {
+        type_params,
+        error: None,
+    };
+    Visit::visit_type(&mut visitor, ty);
+    match visitor.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Returns whether `ty` has a lifetime other than `'static` and the `kept_lifetimes`.
+///
+/// Lifetimes introduced by `for<'x>`, like in `for<'x> fn(&'x str)`, aren't generic lifetimes
+/// of the type, so they're ignored.
+pub(crate) fn type_has_generic_lifetime(ty: &Type, kept_lifetimes: &[Lifetime]) -> bool {
+    struct Visitor<'a> {
+        kept_lifetimes: &'a [Lifetime],
+        bound_lifetimes: Vec<Lifetime>,
         has_generic_lifetime: bool,
     }
-    impl Visit<'_> for Visitor {
+    impl Visitor<'_> {
+        fn visit_with_bound_lifetimes(
+            &mut self,
+            bound_lifetimes: Option<&BoundLifetimes>,
+            visit: impl FnOnce(&mut Self),
+        ) {
+            let len = self.bound_lifetimes.len();
+            self.bound_lifetimes.extend(
+                bound_lifetimes
+                    .into_iter()
+                    .flat_map(|bound_lifetimes| &bound_lifetimes.lifetimes)
+                    .map(|def| def.lifetime.clone()),
+            );
+            visit(self);
+            self.bound_lifetimes.truncate(len);
+        }
+    }
+    impl Visit<'_> for Visitor<'_> {
         fn visit_lifetime(&mut self, lifetime: &Lifetime) {
-            if lifetime.ident != "static" {
+            if lifetime.ident != "static"
+                && !self.kept_lifetimes.contains(lifetime)
+                && !self.bound_lifetimes.contains(lifetime)
+            {
                 self.has_generic_lifetime = true
             } else {
                 visit::visit_lifetime(self, lifetime)
             }
         }
+
+        fn visit_type_bare_fn(&mut self, bare_fn: &TypeBareFn) {
+            self.visit_with_bound_lifetimes(bare_fn.lifetimes.as_ref(), |visitor| {
+                visit::visit_type_bare_fn(visitor, bare_fn)
+            })
+        }
+
+        fn visit_trait_bound(&mut self, trait_bound: &TraitBound) {
+            self.visit_with_bound_lifetimes(trait_bound.lifetimes.as_ref(), |visitor| {
+                visit::visit_trait_bound(visitor, trait_bound)
+            })
+        }
     }
     let mut visitor = Visitor {
+        kept_lifetimes,
+        bound_lifetimes: Vec::new(),
         has_generic_lifetime: false,
     };
     Visit::visit_type(&mut visitor, ty);
     visitor.has_generic_lifetime
 }
+
+pub(crate) fn type_has_type_param(ty: &Type, type_params: &[Ident]) -> bool {
+    struct Visitor<'a> {
+        type_params: &'a [Ident],
+        has_type_param: bool,
+    }
+    impl Visit<'_> for Visitor<'_> {
+        fn visit_type_path(&mut self, type_path: &TypePath) {
+            if type_path.qself.is_none() && type_path.path.leading_colon.is_none() {
+                if let Some(segment) = type_path.path.segments.first() {
+                    if self.type_params.contains(&segment.ident) {
+                        self.has_type_param = true;
+                        return;
+                    }
+                }
+            }
+            visit::visit_type_path(self, type_path)
+        }
+    }
+    let mut visitor = Visitor {
+        type_params,
+        has_type_param: false,
+    };
+    Visit::visit_type(&mut visitor, ty);
+    visitor.has_type_param
+}